@@ -1,35 +1,159 @@
+use std::env;
 use std::fmt::Debug;
+use std::hint::black_box;
+use std::str::FromStr;
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, Criterion, Throughput, criterion_group, criterion_main};
 use strum::IntoEnumIterator;
 
-use aoc_rust::all_challenge_days;
-use aoc_rust::challenge::{Day, DayWrapper, Part};
+use aoc_rust::challenge::{Day, DayWrapper, ParsedInput, Part};
+use aoc_rust::select_challenge_days;
+
+/// What to benchmark, from the environment (criterion owns the command line arguments):
+/// `AOC_YEAR`, `AOC_DAY` and `AOC_PART` select days and parts like the runner's `--year`, `--day` and
+/// `--part`, and `AOC_BENCH_EXAMPLES=1` benches the examples instead of the inputs.
+struct Selection {
+    year: Option<i32>,
+    day: Option<u32>,
+    part: Option<Part>,
+    examples: bool,
+}
+
+impl Selection {
+    fn from_env() -> Self {
+        Self {
+            year: env_var("AOC_YEAR"),
+            day: env_var("AOC_DAY"),
+            part: env_var::<u8>("AOC_PART").map(|part| Part::try_from(part).unwrap()),
+            examples: env::var("AOC_BENCH_EXAMPLES").is_ok_and(|v| v == "1"),
+        }
+    }
+}
+
+fn env_var<V: FromStr>(name: &str) -> Option<V> {
+    let value = env::var(name).ok()?;
+    let parsed = value
+        .parse()
+        .unwrap_or_else(|_| panic!("Invalid {name}: {value}"));
+    Some(parsed)
+}
 
 fn bench_challenge_days(c: &mut Criterion) {
-    for day in all_challenge_days() {
+    let selection = Selection::from_env();
+    for day in select_challenge_days(selection.year, selection.day).unwrap() {
         match day {
-            DayWrapper::I32(day) => bench_day(c, &day),
-            DayWrapper::U32(day) => bench_day(c, &day),
-            DayWrapper::U64(day) => bench_day(c, &day),
-            DayWrapper::Usize(day) => bench_day(c, &day),
-            DayWrapper::String(day) => bench_day(c, &day),
+            DayWrapper::I32(day) => bench_day(c, &day, &selection),
+            DayWrapper::U32(day) => bench_day(c, &day, &selection),
+            DayWrapper::U64(day) => bench_day(c, &day, &selection),
+            DayWrapper::Usize(day) => bench_day(c, &day, &selection),
+            DayWrapper::String(day) => bench_day(c, &day, &selection),
         }
     }
 }
 
-fn bench_day<T>(c: &mut Criterion, day: &Day<T>)
+/// Benches the day's parts (and their variants) in a group named after the day, e.g.
+/// `year2023::day06/part2/slow`, with `/example` appended to the group name for examples.
+///
+/// Answers are checked once up front, keeping assertions out of the measurements.
+fn bench_day<T>(c: &mut Criterion, day: &Day<T>, selection: &Selection)
 where
     T: Eq + Debug,
 {
-    let input_data = day.read_data_file("input").unwrap();
+    let mut group_name = day.day_label().unwrap();
+    if selection.examples {
+        group_name.push_str("/example");
+    }
+    let mut group = c.benchmark_group(group_name);
+    let shared_data = if selection.examples && day.distinct_examples {
+        None
+    } else {
+        let file_name = if selection.examples {
+            "example"
+        } else {
+            "input"
+        };
+        let data = day.read_data_file(file_name).unwrap();
+        bench_parse(&mut group, day, "parse", &data);
+        Some(data)
+    };
     for part in Part::iter() {
-        if let Some((_, Some(expected_value))) = day.solutions(part) {
-            let solver = day.get_solver(part);
-            c.bench_function(day.label(part).unwrap().as_str(), |b| {
-                b.iter(|| assert_eq!(expected_value, &solver(&input_data).unwrap()));
-            });
+        if selection.part.is_some_and(|selected| selected != part) {
+            continue;
         }
+        let Some((example_solution, input_solution)) = day.solutions(part) else {
+            continue;
+        };
+        let (data, expected) = match &shared_data {
+            Some(data) if selection.examples => (data.clone(), Some(example_solution)),
+            Some(data) => (data.clone(), input_solution.as_ref()),
+            None => {
+                let data = day
+                    .read_data_file(&format!("example{}", part as u8))
+                    .unwrap();
+                bench_parse(&mut group, day, &format!("{part}/parse"), &data);
+                (data, Some(example_solution))
+            }
+        };
+        bench_part(&mut group, day, part, &data, selection.examples, expected);
+    }
+    group.finish();
+}
+
+/// Benches the parse step of the day, if it has one.
+fn bench_parse<T>(group: &mut BenchmarkGroup<WallTime>, day: &Day<T>, id: &str, data: &str) {
+    if let Some(step) = &day.parse_step {
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_function(id, |b| b.iter(|| step.parse(black_box(data)).unwrap()));
+    }
+}
+
+/// Benches the part and its variants; with a parse step, the part only measures solving.
+fn bench_part<T>(
+    group: &mut BenchmarkGroup<WallTime>,
+    day: &Day<T>,
+    part: Part,
+    data: &str,
+    example: bool,
+    expected: Option<&T>,
+) where
+    T: Eq + Debug,
+{
+    let check = |label: &str, actual: T| {
+        if let Some(expected) = expected {
+            assert_eq!(expected, &actual, "{label} gives a wrong answer");
+        }
+    };
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    let id = part.to_string();
+    if let Some(step) = &day.parse_step {
+        let input = step.parse(data).unwrap();
+        let solve = |input: &ParsedInput| {
+            if example {
+                step.solve_example(part, input)
+            } else {
+                step.solve(part, input)
+            }
+        };
+        check(&id, solve(&input).unwrap());
+        group.bench_function(&id, |b| b.iter(|| solve(black_box(&input)).unwrap()));
+    } else {
+        let solver = if example {
+            day.get_example_solver(part)
+        } else {
+            day.get_solver(part)
+        };
+        check(&id, solver(data).unwrap());
+        group.bench_function(&id, |b| b.iter(|| solver(black_box(data)).unwrap()));
+    }
+
+    for variant in day.variants(part) {
+        let id = format!("{part}/{}", variant.name);
+        check(&id, (variant.solver)(data).unwrap());
+        group.bench_function(&id, |b| {
+            b.iter(|| (variant.solver)(black_box(data)).unwrap());
+        });
     }
 }
 
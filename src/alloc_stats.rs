@@ -0,0 +1,133 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Heap usage over a measured section, see [`CountingAllocator`].
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct AllocStats {
+    /// Peak heap growth above the usage at the start of the section
+    pub peak: usize,
+    /// Total bytes allocated (reallocations count their new size)
+    pub bytes: usize,
+    pub allocations: usize,
+}
+
+impl AllocStats {
+    /// Stats of this section followed by `next`, e.g. parsing then solving.
+    ///
+    /// Peaks are measured from the start of each section, so their maximum is a lower bound.
+    #[must_use]
+    pub fn then(self, next: Self) -> Self {
+        Self {
+            peak: self.peak.max(next.peak),
+            bytes: self.bytes + next.bytes,
+            allocations: self.allocations + next.allocations,
+        }
+    }
+}
+
+/// System allocator that keeps track of heap usage, installed as the `#[global_allocator]` of the
+/// runner with the `count-allocations` feature.
+pub struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static BASELINE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+impl CountingAllocator {
+    /// Starts a measured section.
+    pub fn reset() {
+        let current = CURRENT.load(Ordering::Relaxed);
+        BASELINE.store(current, Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        BYTES.store(0, Ordering::Relaxed);
+        ALLOCATIONS.store(0, Ordering::Relaxed);
+    }
+
+    /// Heap usage since the last [`reset`](Self::reset).
+    pub fn stats() -> AllocStats {
+        AllocStats {
+            peak: PEAK
+                .load(Ordering::Relaxed)
+                .saturating_sub(BASELINE.load(Ordering::Relaxed)),
+            bytes: BYTES.load(Ordering::Relaxed),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        }
+    }
+
+    fn allocated(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn deallocated(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: all allocations are delegated to the system allocator.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: same contract as `GlobalAlloc::alloc`
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: same contract as `GlobalAlloc::dealloc`
+        unsafe { System.dealloc(ptr, layout) };
+        Self::deallocated(layout.size());
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: same contract as `GlobalAlloc::alloc_zeroed`
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: same contract as `GlobalAlloc::realloc`
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            Self::deallocated(layout.size());
+            Self::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn then_works() {
+        let parse = AllocStats {
+            peak: 100,
+            bytes: 150,
+            allocations: 3,
+        };
+        let solve = AllocStats {
+            peak: 40,
+            bytes: 60,
+            allocations: 2,
+        };
+        assert_eq!(
+            parse.then(solve),
+            AllocStats {
+                peak: 100,
+                bytes: 210,
+                allocations: 5,
+            }
+        );
+    }
+}
@@ -1,4 +1,7 @@
+use std::any::Any;
+use std::cell::RefCell;
 use std::ffi::OsStr;
+use std::sync::OnceLock;
 
 use anyhow::{anyhow, Result};
 use chrono::NaiveDate;
@@ -6,9 +9,143 @@ use num_enum::TryFromPrimitive;
 use strum_macros::EnumIter;
 
 use crate::input::read_data_file;
+use crate::puzzle;
+use crate::utils::graph_export::GraphExport;
+use crate::utils::grid::Grid;
+use crate::utils::grid_image::Rgb;
 
 pub type PartSolutions<T> = (T, Option<T>);
 pub type PartSolver<T> = fn(&str) -> Result<T>;
+pub type GraphExporter = fn(&str) -> Result<GraphExport>;
+/// Renders the puzzle data as colored grids: a still image, or the frames of an animation.
+pub type ImageExporter = fn(&str) -> Result<Vec<Grid<Rgb>>>;
+
+thread_local! {
+    /// Diagnostics recorded by the running solver, when they are collected.
+    static DIAGNOSTICS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Records a diagnostic of the running solver, such as cache statistics, if the caller collects
+/// them (see [`collect_diagnostics`]).
+pub fn diagnostic<F: FnOnce() -> String>(message: F) {
+    DIAGNOSTICS.with_borrow_mut(|diagnostics| {
+        if let Some(diagnostics) = diagnostics {
+            diagnostics.push(message());
+        }
+    });
+}
+
+/// Runs `f`, returning its result along with the diagnostics it recorded.
+pub fn collect_diagnostics<R, F: FnOnce() -> R>(f: F) -> (R, Vec<String>) {
+    let outer = DIAGNOSTICS.replace(Some(vec![]));
+    let result = f();
+    let diagnostics = DIAGNOSTICS.replace(outer).unwrap_or_default();
+    (result, diagnostics)
+}
+
+/// Days whose input is parsed once, then shared by both parts.
+///
+/// Implemented on a marker type, and registered with [`Day::parsed`], which derives both the part
+/// solvers and the parse step (for the runner, which times parsing and solving separately) from it.
+pub trait Parsed {
+    type Input: 'static;
+    type Answer;
+
+    fn parse(data: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer>;
+
+    /// Part 1 of the examples, for days whose examples take other parameters than the input (e.g.
+    /// fewer steps).
+    fn part1_example(input: &Self::Input) -> Result<Self::Answer> {
+        Self::part1(input)
+    }
+
+    /// Part 2 of the examples, see [`Parsed::part1_example`].
+    fn part2_example(input: &Self::Input) -> Result<Self::Answer> {
+        Self::part2(input)
+    }
+}
+
+fn part1_of<D: Parsed>(data: &str) -> Result<D::Answer> {
+    D::part1(&D::parse(data)?)
+}
+
+fn part2_of<D: Parsed>(data: &str) -> Result<D::Answer> {
+    D::part2(&D::parse(data)?)
+}
+
+fn part1_example_of<D: Parsed>(data: &str) -> Result<D::Answer> {
+    D::part1_example(&D::parse(data)?)
+}
+
+fn part2_example_of<D: Parsed>(data: &str) -> Result<D::Answer> {
+    D::part2_example(&D::parse(data)?)
+}
+
+/// Parsed input of a day, with its type erased.
+pub type ParsedInput = Box<dyn Any>;
+
+/// Type-erased [`Parsed`] implementation.
+#[derive(Debug)]
+pub struct ParseStep<T> {
+    parse: fn(&str) -> Result<ParsedInput>,
+    part1: fn(&ParsedInput) -> Result<T>,
+    part2: fn(&ParsedInput) -> Result<T>,
+    part1_example: fn(&ParsedInput) -> Result<T>,
+    part2_example: fn(&ParsedInput) -> Result<T>,
+}
+
+impl<T> ParseStep<T> {
+    pub fn of<D: Parsed<Answer = T>>() -> Self {
+        Self {
+            parse: |data| Ok(Box::new(D::parse(data)?)),
+            part1: |input| D::part1(downcast::<D>(input)?),
+            part2: |input| D::part2(downcast::<D>(input)?),
+            part1_example: |input| D::part1_example(downcast::<D>(input)?),
+            part2_example: |input| D::part2_example(downcast::<D>(input)?),
+        }
+    }
+
+    pub fn parse(&self, data: &str) -> Result<ParsedInput> {
+        (self.parse)(data)
+    }
+
+    pub fn solve(&self, part: Part, input: &ParsedInput) -> Result<T> {
+        match part {
+            Part::Part1 => (self.part1)(input),
+            Part::Part2 => (self.part2)(input),
+        }
+    }
+
+    pub fn solve_example(&self, part: Part, input: &ParsedInput) -> Result<T> {
+        match part {
+            Part::Part1 => (self.part1_example)(input),
+            Part::Part2 => (self.part2_example)(input),
+        }
+    }
+}
+
+fn downcast<D: Parsed>(input: &ParsedInput) -> Result<&D::Input> {
+    input
+        .downcast_ref()
+        .ok_or(anyhow!("Parsed input has an unexpected type"))
+}
+
+/// Alternative implementation of a part, e.g. a naive version of an optimized solver.
+#[derive(Debug)]
+pub struct Variant<T> {
+    pub part: Part,
+    pub name: &'static str,
+    pub solver: PartSolver<T>,
+}
+
+/// Rendering of the puzzle data, such as the final state of a simulation.
+#[derive(Debug)]
+pub struct ImageExport {
+    pub label: &'static str,
+    pub export: ImageExporter,
+}
 
 #[derive(Debug)]
 pub struct Day<T> {
@@ -18,9 +155,70 @@ pub struct Day<T> {
     pub part2_solver: PartSolver<T>,
     pub source_file: &'static str,
     pub distinct_examples: bool,
+    /// Describes the structure of the puzzle data as a graph, for inspection (see `--dump-graph`)
+    pub graph_export: Option<GraphExporter>,
+    /// Renderings of the puzzle data, by label (see `--dump-image`)
+    pub image_exports: Vec<ImageExport>,
+    /// Alternative solvers, checked by the runner against the main solver's answers
+    pub variants: Vec<Variant<T>>,
+    /// Shared parsing for both parts, used by the runner instead of the part solvers (see
+    /// [`Day::parsed`])
+    pub parse_step: Option<ParseStep<T>>,
+    /// Solvers of the examples, when they take other parameters than the input (see
+    /// [`Parsed::part1_example`])
+    pub example_solvers: Option<(PartSolver<T>, PartSolver<T>)>,
+    /// Puzzle title, read from the statement on first use (see [`Day::title`])
+    pub(crate) title: OnceLock<Option<String>>,
+}
+
+/// Day without solutions nor solvers, for the struct update syntax of the days which set the
+/// required fields and only the optional ones they use.
+impl<T: Default> Default for Day<T> {
+    fn default() -> Self {
+        Self {
+            part1_solutions: (T::default(), None),
+            part2_solutions: None,
+            part1_solver: unsolved,
+            part2_solver: unsolved,
+            source_file: "",
+            distinct_examples: false,
+            graph_export: None,
+            image_exports: vec![],
+            variants: vec![],
+            parse_step: None,
+            example_solvers: None,
+            title: OnceLock::new(),
+        }
+    }
+}
+
+fn unsolved<T>(_data: &str) -> Result<T> {
+    Err(anyhow!("No solver"))
 }
 
 impl<T> Day<T> {
+    /// Day whose parts share the parsing of `D`, which provides both the part solvers and the parse
+    /// step. Other fields are the defaults, to override with the struct update syntax.
+    pub fn parsed<D: Parsed<Answer = T>>(
+        part1_solutions: PartSolutions<T>,
+        part2_solutions: Option<PartSolutions<T>>,
+        source_file: &'static str,
+    ) -> Self
+    where
+        T: Default,
+    {
+        Self {
+            part1_solutions,
+            part2_solutions,
+            part1_solver: part1_of::<D>,
+            part2_solver: part2_of::<D>,
+            source_file,
+            parse_step: Some(ParseStep::of::<D>()),
+            example_solvers: Some((part1_example_of::<D>, part2_example_of::<D>)),
+            ..Self::default()
+        }
+    }
+
     pub fn solutions(&self, part: Part) -> Option<&PartSolutions<T>> {
         match part {
             Part::Part1 => Some(&self.part1_solutions),
@@ -35,9 +233,64 @@ impl<T> Day<T> {
         }
     }
 
+    pub fn get_example_solver(&self, part: Part) -> PartSolver<T> {
+        match (part, self.example_solvers) {
+            (Part::Part1, Some((solver, _))) | (Part::Part2, Some((_, solver))) => solver,
+            (_, None) => self.get_solver(part),
+        }
+    }
+
+    pub fn image_export(&self, label: &str) -> Option<ImageExporter> {
+        self.image_exports
+            .iter()
+            .find(|image| image.label == label)
+            .map(|image| image.export)
+    }
+
+    pub fn variants(&self, part: Part) -> impl Iterator<Item = &Variant<T>> {
+        self.variants
+            .iter()
+            .filter(move |variant| variant.part == part)
+    }
+
+    /// e.g. `year2023::day06`
+    pub fn day_label(&self) -> Result<String> {
+        Ok(self.source_file_location()?.label())
+    }
+
+    /// e.g. `year2023::day17::part1`, which identifies the part in timing histories and benches
     pub fn label(&self, part: Part) -> Result<String> {
-        let loc = self.source_file_location()?;
-        Ok(format!("{}::{}::{}", loc.dir, loc.stem, part))
+        Ok(format!("{}::{}", self.day_label()?, part))
+    }
+
+    /// The puzzle title, when the statement was downloaded (only read once).
+    pub fn title(&self) -> Option<&str> {
+        self.title
+            .get_or_init(|| self.source_file_location().ok()?.read_title())
+            .as_deref()
+    }
+
+    /// The day label followed by the puzzle title, if known, for display (e.g.
+    /// `year2023::day17 Clumsy Crucible`).
+    pub fn titled_day_label(&self) -> Result<String> {
+        Ok(self.with_title(self.day_label()?))
+    }
+
+    /// The part label followed by the puzzle title, if known, for display (e.g.
+    /// `year2023::day17::part1 Clumsy Crucible`).
+    pub fn titled_label(&self, part: Part) -> Result<String> {
+        Ok(self.with_title(self.label(part)?))
+    }
+
+    fn with_title(&self, label: String) -> String {
+        match self.title() {
+            Some(title) => format!("{label} {title}"),
+            None => label,
+        }
+    }
+
+    pub fn variant_label(&self, part: Part, variant: &str) -> Result<String> {
+        Ok(format!("{}/{}", self.label(part)?, variant))
     }
 
     fn source_file_location(&self) -> Result<SourceFileLocation> {
@@ -71,6 +324,17 @@ pub struct SourceFileLocation {
 }
 
 impl SourceFileLocation {
+    /// e.g. `year2023::day17`
+    pub fn label(&self) -> String {
+        format!("{}::{}", self.dir, self.stem)
+    }
+
+    /// The puzzle title, when the statement was downloaded (cached by [`Day::title`]).
+    fn read_title(&self) -> Option<String> {
+        let markdown = read_data_file(&self.dir, &self.stem, "puzzle.md").ok()?;
+        puzzle::title(&markdown).map(ToOwned::to_owned)
+    }
+
     pub fn date(&self) -> Result<NaiveDate> {
         let year = self
             .dir
@@ -87,7 +351,9 @@ impl SourceFileLocation {
     }
 }
 
-#[derive(Debug, EnumIter, strum_macros::Display, Copy, Clone, Eq, PartialEq, TryFromPrimitive)]
+#[derive(
+    Debug, EnumIter, strum_macros::Display, Copy, Clone, Eq, PartialEq, Hash, TryFromPrimitive,
+)]
 #[strum(serialize_all = "lowercase")]
 #[repr(u8)]
 pub enum Part {
@@ -115,6 +381,18 @@ impl DayWrapper {
             String(day) => day.source_file_location(),
         }
     }
+
+    /// See [`Day::titled_day_label`].
+    pub fn titled_label(&self) -> Result<String> {
+        use DayWrapper::*;
+        match self {
+            I32(day) => day.titled_day_label(),
+            U32(day) => day.titled_day_label(),
+            U64(day) => day.titled_day_label(),
+            Usize(day) => day.titled_day_label(),
+            String(day) => day.titled_day_label(),
+        }
+    }
 }
 
 impl From<Day<i32>> for DayWrapper {
@@ -142,3 +420,54 @@ impl From<Day<String>> for DayWrapper {
         DayWrapper::String(day)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Parsed for Sum {
+        type Input = Vec<u32>;
+        type Answer = u32;
+
+        fn parse(data: &str) -> Result<Vec<u32>> {
+            Ok(data.split(',').map(str::parse).collect::<Result<_, _>>()?)
+        }
+
+        fn part1(input: &Vec<u32>) -> Result<u32> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Vec<u32>) -> Result<u32> {
+            Ok(input.iter().product())
+        }
+
+        fn part1_example(input: &Vec<u32>) -> Result<u32> {
+            Ok(input.iter().take(2).sum())
+        }
+    }
+
+    #[test]
+    fn parse_step_works() {
+        let day = Day::parsed::<Sum>((6, None), None, file!());
+        let step = day.parse_step.as_ref().unwrap();
+        let input = step.parse("1,2,3").unwrap();
+        assert_eq!(step.solve(Part::Part1, &input).unwrap(), 6);
+        assert_eq!(step.solve(Part::Part2, &input).unwrap(), 6);
+        assert_eq!(day.get_solver(Part::Part1)("1,2,3").unwrap(), 6);
+        assert_eq!(step.solve_example(Part::Part1, &input).unwrap(), 3);
+        assert_eq!(step.solve_example(Part::Part2, &input).unwrap(), 6);
+        assert_eq!(day.get_example_solver(Part::Part1)("1,2,3").unwrap(), 3);
+
+        let other: ParsedInput = Box::new("1,2,3");
+        assert!(step.solve(Part::Part1, &other).is_err());
+    }
+
+    #[test]
+    fn diagnostics_are_collected() {
+        diagnostic(|| unreachable!("not collected"));
+        let ((), diagnostics) = collect_diagnostics(|| diagnostic(|| "cache: 1 hit".to_owned()));
+        assert_eq!(diagnostics, ["cache: 1 hit"]);
+    }
+}
@@ -0,0 +1,250 @@
+//! Terminal dashboard of all days, in a calendar layout.
+
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::Datelike;
+use itertools::Itertools;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use strum::IntoEnumIterator;
+
+use crate::all_challenge_days;
+use crate::challenge::Part;
+use crate::input::read_data_file;
+use runner::{Answer, Data, Outcome, Request};
+
+mod runner;
+mod ui;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Days of the month shown in the calendar.
+const CALENDAR_DAYS: u32 = 25;
+
+/// Runs the dashboard until it is quit, starting on the given year and day (by default, the
+/// latest day).
+pub fn run(year: Option<i32>, day: Option<u32>) -> Result<()> {
+    let (requests, outcomes) = runner::spawn()?;
+    let mut app = App::new(requests, year, day)?;
+
+    let mut terminal = ratatui::init();
+    // after ratatui's hook (which restores the terminal), keep the solving thread's panics quiet
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() != Some(runner::THREAD_NAME) {
+            default_hook(info);
+        }
+    }));
+
+    let result = (|| -> Result<()> {
+        while !app.quit {
+            terminal.draw(|frame| ui::draw(frame, &app))?;
+            if event::poll(POLL_INTERVAL)?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                app.on_key(key.code);
+            }
+            for outcome in outcomes.try_iter() {
+                app.on_outcome(outcome);
+            }
+        }
+        Ok(())
+    })();
+
+    ratatui::restore();
+    drop(std::panic::take_hook()); // back to the default hook
+    result
+}
+
+/// A registered day.
+struct DayEntry {
+    year: i32,
+    day: u32,
+    label: String,
+    puzzle: Option<String>,
+}
+
+enum RunState {
+    Running,
+    Done(Result<Answer, String>),
+}
+
+struct App {
+    days: Vec<DayEntry>,
+    years: Vec<i32>,
+    /// Selected calendar cell
+    year: i32,
+    day: u32,
+    /// Part to run, both if unspecified
+    part: Option<Part>,
+    results: HashMap<(usize, Part, Data), RunState>,
+    puzzle_scroll: u16,
+    requests: Sender<Request>,
+    quit: bool,
+}
+
+impl App {
+    fn new(requests: Sender<Request>, year: Option<i32>, day: Option<u32>) -> Result<Self> {
+        let mut days = vec![];
+        for challenge_day in all_challenge_days() {
+            let loc = challenge_day.source_file_location()?;
+            let date = loc.date()?;
+            days.push(DayEntry {
+                year: date.year(),
+                day: date.day(),
+                label: challenge_day.titled_label()?,
+                puzzle: read_data_file(&loc.dir, &loc.stem, "puzzle.md").ok(),
+            });
+        }
+        let years = days.iter().map(|entry| entry.year).dedup().collect_vec();
+        let latest = days
+            .last()
+            .map_or((2025, 1), |entry| (entry.year, entry.day));
+        Ok(Self {
+            days,
+            years,
+            year: year.unwrap_or(latest.0),
+            day: day.unwrap_or(if year.is_some() { 1 } else { latest.1 }),
+            part: None,
+            results: HashMap::new(),
+            puzzle_scroll: 0,
+            requests,
+            quit: false,
+        })
+    }
+
+    /// Index of the registered day in the calendar cell, if any.
+    fn day_index(&self, year: i32, day: u32) -> Option<usize> {
+        self.days
+            .iter()
+            .position(|entry| entry.year == year && entry.day == day)
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.day_index(self.year, self.day)
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        self.part
+            .map_or_else(|| Part::iter().collect(), |part| vec![part])
+    }
+
+    fn on_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Left | KeyCode::Char('h') => self.select_day(self.day.saturating_sub(1)),
+            KeyCode::Right | KeyCode::Char('l') => self.select_day(self.day + 1),
+            KeyCode::Up | KeyCode::Char('k') => self.select_day(self.day.saturating_sub(5)),
+            KeyCode::Down | KeyCode::Char('j') => self.select_day(self.day + 5),
+            KeyCode::Char('[') => self.select_year(-1),
+            KeyCode::Char(']') => self.select_year(1),
+            KeyCode::Char('p') => {
+                self.part = match self.part {
+                    None => Some(Part::Part1),
+                    Some(Part::Part1) => Some(Part::Part2),
+                    Some(Part::Part2) => None,
+                };
+            }
+            KeyCode::Enter | KeyCode::Char('r') => {
+                if let Some(day) = self.selected() {
+                    self.request(day);
+                }
+            }
+            KeyCode::Char('a') => {
+                let year = self.year;
+                let days = (0..self.days.len())
+                    .filter(|&day| self.days[day].year == year)
+                    .collect_vec();
+                for day in days {
+                    self.request(day);
+                }
+            }
+            KeyCode::PageDown | KeyCode::Char('d') => {
+                self.puzzle_scroll = self.puzzle_scroll.saturating_add(10);
+            }
+            KeyCode::PageUp | KeyCode::Char('u') => {
+                self.puzzle_scroll = self.puzzle_scroll.saturating_sub(10);
+            }
+            _ => {}
+        }
+    }
+
+    fn select_day(&mut self, day: u32) {
+        let day = day.clamp(1, CALENDAR_DAYS);
+        if day != self.day {
+            self.day = day;
+            self.puzzle_scroll = 0;
+        }
+    }
+
+    fn select_year(&mut self, offset: isize) {
+        let Some(index) = self.years.iter().position(|&year| year == self.year) else {
+            return;
+        };
+        if let Some(&year) = index
+            .checked_add_signed(offset)
+            .and_then(|index| self.years.get(index))
+        {
+            self.year = year;
+            self.puzzle_scroll = 0;
+        }
+    }
+
+    fn request(&mut self, day: usize) {
+        let parts = self.parts();
+        for &part in &parts {
+            for data in [Data::Example, Data::Input] {
+                self.results.insert((day, part, data), RunState::Running);
+            }
+        }
+        // the runner only stops with the dashboard
+        self.requests.send(Request { day, parts }).ok();
+    }
+
+    fn on_outcome(&mut self, outcome: Outcome) {
+        let key = (outcome.day, outcome.part, outcome.data);
+        match outcome.result {
+            Some(result) => self.results.insert(key, RunState::Done(result)),
+            None => self.results.remove(&key),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn dashboard_works() {
+        let (requests, outcomes) = runner::spawn().unwrap();
+        let mut app = App::new(requests, Some(2025), Some(1)).unwrap();
+        app.on_key(KeyCode::Char('r'));
+        for _ in 0..4 {
+            app.on_outcome(outcomes.recv().unwrap());
+        }
+        app.on_key(KeyCode::Right);
+        app.on_key(KeyCode::Left);
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|frame| ui::draw(frame, &app)).unwrap();
+        let screen = terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(120)
+            .map(|row| {
+                row.iter()
+                    .map(ratatui::buffer::Cell::symbol)
+                    .collect::<String>()
+            })
+            .join("\n");
+        assert!(screen.contains("1 ✓✓"));
+        assert!(screen.contains("part2 input    OK"));
+        assert!(screen.contains("--- Day 1"));
+    }
+}
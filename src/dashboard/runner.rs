@@ -0,0 +1,164 @@
+use std::fmt::Debug;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::all_challenge_days;
+use crate::challenge::{Day, DayWrapper, Part};
+
+/// Name of the solving thread, whose panics are reported as errors instead of being printed.
+pub const THREAD_NAME: &str = "dashboard-runner";
+
+#[derive(Debug, strum_macros::Display, Copy, Clone, Eq, PartialEq, Hash)]
+#[strum(serialize_all = "lowercase")]
+pub enum Data {
+    Example,
+    Input,
+}
+
+/// Parts of a day to solve, the day being an index into [`all_challenge_days`].
+#[derive(Debug)]
+pub struct Request {
+    pub day: usize,
+    pub parts: Vec<Part>,
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: usize,
+    pub part: Part,
+    pub data: Data,
+    /// The answer, or the error (or panic) message, unless the part has no solver (e.g. the last
+    /// day's part 2)
+    pub result: Option<Result<Answer, String>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Answer {
+    pub value: String,
+    /// Unless the answer is not known yet
+    pub expected: Option<String>,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Status {
+    Ok,
+    Fail,
+    New,
+}
+
+impl Answer {
+    pub fn status(&self) -> Status {
+        match &self.expected {
+            Some(expected) if *expected == self.value => Status::Ok,
+            Some(_) => Status::Fail,
+            None => Status::New,
+        }
+    }
+}
+
+/// Starts the thread solving requested parts, on the example then the input, one outcome at a time.
+pub fn spawn() -> Result<(Sender<Request>, Receiver<Outcome>)> {
+    let (request_tx, request_rx) = mpsc::channel::<Request>();
+    let (outcome_tx, outcome_rx) = mpsc::channel();
+    std::thread::Builder::new()
+        .name(THREAD_NAME.to_owned())
+        .spawn(move || {
+            let days = all_challenge_days();
+            for request in request_rx {
+                for &part in &request.parts {
+                    for data in [Data::Example, Data::Input] {
+                        let result = match &days[request.day] {
+                            DayWrapper::I32(day) => solve(day, part, data),
+                            DayWrapper::U32(day) => solve(day, part, data),
+                            DayWrapper::U64(day) => solve(day, part, data),
+                            DayWrapper::Usize(day) => solve(day, part, data),
+                            DayWrapper::String(day) => solve(day, part, data),
+                        };
+                        let outcome = Outcome {
+                            day: request.day,
+                            part,
+                            data,
+                            result,
+                        };
+                        if outcome_tx.send(outcome).is_err() {
+                            return; // the dashboard is gone
+                        }
+                    }
+                }
+            }
+        })?;
+    Ok((request_tx, outcome_rx))
+}
+
+fn solve<T>(day: &Day<T>, part: Part, data: Data) -> Option<Result<Answer, String>>
+where
+    T: Eq + Debug,
+{
+    let (example_solution, input_solution) = day.solutions(part)?;
+    let (file_name, expected) = match data {
+        Data::Example if day.distinct_examples => {
+            (format!("example{}", part as u8), Some(example_solution))
+        }
+        Data::Example => (data.to_string(), Some(example_solution)),
+        Data::Input => (data.to_string(), input_solution.as_ref()),
+    };
+    let text = match day.read_data_file(&file_name) {
+        Ok(text) => text,
+        Err(e) => return Some(Err(format!("{e:#}"))),
+    };
+    let solver = match data {
+        Data::Example => day.get_example_solver(part),
+        Data::Input => day.get_solver(part),
+    };
+    let start = Instant::now();
+    let result = catch_unwind(AssertUnwindSafe(|| solver(&text)));
+    let duration = start.elapsed();
+    Some(match result {
+        Ok(Ok(value)) => Ok(Answer {
+            value: format!("{value:?}"),
+            expected: expected.map(|expected| format!("{expected:?}")),
+            duration,
+        }),
+        Ok(Err(e)) => Err(format!("{e:#}")),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| (*message).to_owned())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("panicked: {message}"))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runner_works() {
+        let day = all_challenge_days()
+            .iter()
+            .position(|day| {
+                let loc = day.source_file_location().unwrap();
+                loc.dir == "year2025" && loc.stem == "day01"
+            })
+            .unwrap();
+        let (requests, outcomes) = spawn().unwrap();
+        requests
+            .send(Request {
+                day,
+                parts: vec![Part::Part1],
+            })
+            .unwrap();
+        let outcome = outcomes.recv().unwrap();
+        assert_eq!((outcome.part, outcome.data), (Part::Part1, Data::Example));
+        let answer = outcome.result.unwrap().unwrap();
+        assert_eq!(answer.status(), Status::Ok);
+        assert_eq!(outcomes.recv().unwrap().data, Data::Input);
+    }
+}
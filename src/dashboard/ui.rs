@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use strum::IntoEnumIterator;
+
+use super::runner::{Data, Status};
+use super::{App, CALENDAR_DAYS, RunState};
+use crate::challenge::Part;
+
+const CELL_WIDTH: u16 = 9;
+const CALENDAR_COLUMNS: u32 = 5;
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, footer] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+    let calendar_width = CELL_WIDTH * u16::try_from(CALENDAR_COLUMNS).unwrap_or_default() + 2;
+    let [left, puzzle] =
+        Layout::horizontal([Constraint::Length(calendar_width + 8), Constraint::Fill(1)])
+            .areas(main);
+    let calendar_height =
+        u16::try_from(CALENDAR_DAYS.div_ceil(CALENDAR_COLUMNS)).unwrap_or_default() + 2;
+    let [calendar, details] =
+        Layout::vertical([Constraint::Length(calendar_height), Constraint::Fill(1)]).areas(left);
+
+    draw_calendar(frame, app, calendar);
+    draw_details(frame, app, details);
+    draw_puzzle(frame, app, puzzle);
+    draw_footer(frame, app, footer);
+}
+
+/// State of a part over the example and the input, worst first.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+enum Summary {
+    Error,
+    Fail,
+    Running,
+    New,
+    Ok,
+}
+
+fn summary(app: &App, day: usize, part: Part) -> Option<Summary> {
+    [Data::Example, Data::Input]
+        .iter()
+        .filter_map(|&data| app.results.get(&(day, part, data)))
+        .map(|state| match state {
+            RunState::Running => Summary::Running,
+            RunState::Done(Err(_)) => Summary::Error,
+            RunState::Done(Ok(answer)) => match answer.status() {
+                Status::Ok => Summary::Ok,
+                Status::Fail => Summary::Fail,
+                Status::New => Summary::New,
+            },
+        })
+        .min()
+}
+
+fn symbol(summary: Option<Summary>) -> Span<'static> {
+    match summary {
+        None => "·".dark_gray(),
+        Some(Summary::Ok) => "✓".green(),
+        Some(Summary::New) => "?".yellow(),
+        Some(Summary::Running) => "…".cyan(),
+        Some(Summary::Fail) => "✗".red(),
+        Some(Summary::Error) => "!".red().bold(),
+    }
+}
+
+fn draw_calendar(frame: &mut Frame, app: &App, area: Rect) {
+    let years = app
+        .years
+        .iter()
+        .map(|&year| {
+            let span = Span::raw(format!(" {year} "));
+            if year == app.year {
+                span.bold().reversed()
+            } else {
+                span
+            }
+        })
+        .collect::<Vec<_>>();
+    let rows = (0..CALENDAR_DAYS.div_ceil(CALENDAR_COLUMNS))
+        .map(|row| {
+            let cells = (1..=CALENDAR_COLUMNS)
+                .map(|column| row * CALENDAR_COLUMNS + column)
+                .filter(|&day| day <= CALENDAR_DAYS)
+                .flat_map(|day| calendar_cell(app, day));
+            Line::from(cells.collect::<Vec<_>>())
+        })
+        .collect::<Vec<_>>();
+    let block = Block::bordered().title(Line::from(years));
+    frame.render_widget(Paragraph::new(rows).block(block), area);
+}
+
+fn calendar_cell(app: &App, day: u32) -> Vec<Span<'static>> {
+    let spans = match app.day_index(app.year, day) {
+        Some(index) => vec![
+            Span::raw(format!("  {day:>2} ")).bold(),
+            symbol(summary(app, index, Part::Part1)),
+            symbol(summary(app, index, Part::Part2)),
+            Span::raw("  "),
+        ],
+        None => vec![Span::raw(format!("  {day:>2}     ")).dark_gray()],
+    };
+    if day == app.day {
+        spans.into_iter().map(Span::reversed).collect()
+    } else {
+        spans
+    }
+}
+
+fn draw_details(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(index) = app.selected() else {
+        let block = Block::bordered().title(format!(" year{} day{:02} ", app.year, app.day));
+        let text = Paragraph::new("Not solved yet".dark_gray()).block(block);
+        frame.render_widget(text, area);
+        return;
+    };
+    let mut lines = vec![];
+    for part in Part::iter() {
+        for data in [Data::Example, Data::Input] {
+            let mut line = vec![Span::raw(format!("{part} {data:<8} "))];
+            line.extend(match app.results.get(&(index, part, data)) {
+                None => vec!["not run".dark_gray()],
+                Some(RunState::Running) => vec!["running…".cyan()],
+                Some(RunState::Done(Err(message))) => {
+                    vec!["ERROR ".red().bold(), Span::raw(message.clone()).red()]
+                }
+                Some(RunState::Done(Ok(answer))) => {
+                    let duration = Span::raw(format!(" [{}]", format_duration(answer.duration)));
+                    match (answer.status(), &answer.expected) {
+                        (Status::Fail, Some(expected)) => vec![
+                            "FAIL ".red().bold(),
+                            Span::raw(answer.value.clone()).red(),
+                            format!(" (expected {expected})").green(),
+                            duration.yellow(),
+                        ],
+                        (Status::New, _) => vec![
+                            "NEW ".cyan(),
+                            Span::raw(answer.value.clone()).cyan().bold(),
+                            duration.yellow(),
+                        ],
+                        _ => vec![
+                            "OK ".green(),
+                            Span::raw(answer.value.clone()),
+                            duration.yellow(),
+                        ],
+                    }
+                }
+            });
+            lines.push(Line::from(line));
+        }
+    }
+    let block = Block::bordered().title(format!(" {} ", app.days[index].label));
+    let details = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(details, area);
+}
+
+fn draw_puzzle(frame: &mut Frame, app: &App, area: Rect) {
+    let text = app
+        .selected()
+        .and_then(|index| app.days[index].puzzle.as_deref())
+        .unwrap_or("No puzzle.md");
+    let puzzle = Paragraph::new(text)
+        .block(Block::bordered().title(" Puzzle "))
+        .wrap(Wrap { trim: false })
+        .scroll((app.puzzle_scroll, 0));
+    frame.render_widget(puzzle, area);
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let part = app.part.map_or("both".to_owned(), |part| part.to_string());
+    let help = format!(
+        " ←↓↑→ day  [ ] year  p part ({part})  ⏎/r run  a run year  u/d scroll puzzle  q quit"
+    );
+    frame.render_widget(Paragraph::new(help).style(Style::new().dark_gray()), area);
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration.as_millis() >= 1 {
+        format!("{:.1} ms", duration.as_secs_f64() * 1e3)
+    } else {
+        format!("{:.1} µs", duration.as_secs_f64() * 1e6)
+    }
+}
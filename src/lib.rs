@@ -1,15 +1,23 @@
 use crate::challenge::DayWrapper;
+use anyhow::Result;
+use chrono::Datelike;
 use itertools::Itertools;
 
+pub mod alloc_stats;
 pub mod challenge;
+#[cfg(feature = "tui")]
+pub mod dashboard;
 pub mod input;
+pub mod puzzle;
 #[allow(dead_code)]
 #[allow(unreachable_code)]
 #[allow(unused_variables)]
 mod template;
 #[cfg(test)]
 mod testing;
+pub mod timing_history;
 pub mod utils;
+pub mod watch;
 #[cfg(feature = "previous-years")]
 pub mod year2022;
 #[cfg(feature = "previous-years")]
@@ -33,3 +41,15 @@ pub fn all_challenge_days() -> Vec<DayWrapper> {
     .flatten()
     .collect_vec()
 }
+
+/// Challenge days of the given year and/or day of the month (all days when unspecified).
+pub fn select_challenge_days(year: Option<i32>, day: Option<u32>) -> Result<Vec<DayWrapper>> {
+    let mut selected = vec![];
+    for challenge_day in all_challenge_days() {
+        let date = challenge_day.source_file_location()?.date()?;
+        if year.is_none_or(|year| date.year() == year) && day.is_none_or(|day| date.day() == day) {
+            selected.push(challenge_day);
+        }
+    }
+    Ok(selected)
+}
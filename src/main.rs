@@ -1,34 +1,103 @@
 use std::fmt::Debug;
-use std::process::ExitCode;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
 use std::time::{Duration, Instant};
 
-use anyhow::Result;
-use chrono::Datelike;
-use clap::Parser;
+use anyhow::{Result, anyhow, bail};
+use clap::{Parser, Subcommand};
 use colored::Colorize;
 use itertools::Itertools;
 use strum::IntoEnumIterator;
 
-use aoc_rust::all_challenge_days;
-use aoc_rust::challenge::{Day, DayWrapper, Part};
+#[cfg(feature = "count-allocations")]
+use aoc_rust::alloc_stats::{AllocStats, CountingAllocator};
+use aoc_rust::challenge::{Day, DayWrapper, ParsedInput, Part, collect_diagnostics};
+use aoc_rust::input::read_data_file;
+use aoc_rust::puzzle;
+use aoc_rust::select_challenge_days;
+use aoc_rust::timing_history::{TimingHistory, median};
+use aoc_rust::utils::grid::Grid;
+use aoc_rust::utils::grid_image::{GridImage, Rgb};
+use aoc_rust::watch::{self, Snapshot, parse_report};
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 /// Simple program to greet a person
 #[derive(Parser, Debug)]
+#[allow(clippy::struct_excessive_bools)] // command line flags
 struct Args {
-    #[arg(long)]
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    #[arg(long, global = true)]
     year: Option<i32>,
-    #[arg(long)]
+    #[arg(long, global = true)]
     day: Option<u32>,
 
     /// Whether only the latest days should be checked (default: all available days)
-    #[arg(long)]
+    #[arg(long, global = true)]
     latest: bool,
 
-    #[arg(long)]
+    #[arg(long, global = true)]
     part: Option<u8>,
 
     #[arg(long, value_enum)]
     only: Option<Only>,
+
+    /// Print solver diagnostics (e.g. cache statistics)
+    #[arg(long)]
+    verbose: bool,
+
+    /// Write the graph structure of the selected day's input to a file instead of solving it
+    /// (Graphviz DOT, or Mermaid for `.mmd` files)
+    #[arg(long, value_name = "FILE")]
+    dump_graph: Option<PathBuf>,
+
+    /// Write the rendering of the selected day's input with this label to a file instead of
+    /// solving it (PNG, animated GIF or SVG, by extension; LABEL.png, or LABEL.gif for
+    /// animations, by default)
+    #[arg(long, value_names = ["LABEL", "FILE"], num_args = 1..=2, conflicts_with = "dump_graph")]
+    dump_image: Option<Vec<String>>,
+
+    /// Re-run the selected day whenever its source or data files change (rebuilding the runner),
+    /// showing which answers changed
+    #[arg(long, conflicts_with_all = ["dump_graph", "dump_image", "report"])]
+    watch: bool,
+
+    /// Browse all days in a terminal dashboard, running them on demand
+    #[cfg(feature = "tui")]
+    #[arg(long, conflicts_with_all = ["dump_graph", "dump_image", "report", "watch"])]
+    tui: bool,
+
+    /// Run each solver N times, reporting the median duration
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    repeat: u32,
+
+    /// Flag parts that got slower than the median of their previous runs (and fail)
+    #[arg(long)]
+    compare: bool,
+
+    /// Slowdown flagged by --compare, in percent
+    #[arg(long, value_name = "PERCENT", default_value_t = 25.0)]
+    threshold: f64,
+
+    /// File the durations of each run are recorded to
+    #[arg(long, value_name = "FILE", default_value = "timings.tsv")]
+    history: PathBuf,
+
+    /// Write the measurements of each part (and heap usage, when counting allocations) as JSON
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Print the selected day's puzzle statement (only the selected part's, if any)
+    Show,
 }
 
 #[derive(Debug, Copy, Clone, clap::ValueEnum, Eq, PartialEq)]
@@ -41,42 +110,98 @@ enum Only {
 fn main() -> Result<ExitCode> {
     let args = Args::parse();
 
-    let mut days = all_challenge_days();
-
-    days = days
-        .into_iter()
-        .filter(|day| {
-            let date = day.source_file_location().unwrap().date().unwrap();
-            if let Some(year) = args.year
-                && date.year() != year
-            {
-                return false;
-            }
-            if let Some(day) = args.day
-                && date.day() != day
-            {
-                return false;
-            }
-            true
-        })
-        .collect_vec();
+    let days = select_challenge_days(args.year, args.day)?;
 
     let challenge_days = if args.latest {
         days.into_iter().rev().take(1).collect_vec()
     } else {
         days
     };
+
     let part = args.part.map(Part::try_from).transpose()?;
+
+    if let Some(Commands::Show) = args.command {
+        let [day] = challenge_days.as_slice() else {
+            bail!("show requires selecting a single day");
+        };
+        show(day, part)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    #[cfg(feature = "tui")]
+    if args.tui {
+        aoc_rust::dashboard::run(args.year, args.day)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(path) = &args.dump_graph {
+        let [day] = challenge_days.as_slice() else {
+            bail!("--dump-graph requires selecting a single day");
+        };
+        match day {
+            DayWrapper::I32(day) => dump_graph(day, path)?,
+            DayWrapper::U32(day) => dump_graph(day, path)?,
+            DayWrapper::U64(day) => dump_graph(day, path)?,
+            DayWrapper::Usize(day) => dump_graph(day, path)?,
+            DayWrapper::String(day) => dump_graph(day, path)?,
+        }
+        println!(
+            "Wrote graph of {} to {}",
+            day.titled_label()?,
+            path.display()
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(values) = &args.dump_image {
+        let [day] = challenge_days.as_slice() else {
+            bail!("--dump-image requires selecting a single day");
+        };
+        dump_image(day, &values[0], values.get(1).map(PathBuf::from))?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if args.watch {
+        let [day] = challenge_days.as_slice() else {
+            bail!("--watch requires selecting a single day");
+        };
+        watch(day)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut timings = Timings {
+        repeat: args.repeat,
+        verbose: args.verbose,
+        regression_threshold: args.compare.then_some(args.threshold / 100.0),
+        history: TimingHistory::load(&args.history)?,
+        build: format!(
+            "{} {} x{}",
+            if cfg!(debug_assertions) {
+                "debug"
+            } else {
+                "release"
+            },
+            enabled_features(),
+            args.repeat
+        ),
+        regressions: 0,
+        report: vec![],
+    };
     let mut ok = true;
     for day in &challenge_days {
         ok &= match day {
-            DayWrapper::I32(day) => check_day(day, part, args.only)?,
-            DayWrapper::U32(day) => check_day(day, part, args.only)?,
-            DayWrapper::U64(day) => check_day(day, part, args.only)?,
-            DayWrapper::Usize(day) => check_day(day, part, args.only)?,
-            DayWrapper::String(day) => check_day(day, part, args.only)?,
+            DayWrapper::I32(day) => check_day(day, part, args.only, &mut timings)?,
+            DayWrapper::U32(day) => check_day(day, part, args.only, &mut timings)?,
+            DayWrapper::U64(day) => check_day(day, part, args.only, &mut timings)?,
+            DayWrapper::Usize(day) => check_day(day, part, args.only, &mut timings)?,
+            DayWrapper::String(day) => check_day(day, part, args.only, &mut timings)?,
         };
     }
+    timings.history.save()?;
+    ok &= timings.regressions == 0;
+    if let Some(path) = &args.report {
+        std::fs::write(path, serde_json::to_string_pretty(&timings.report)?)?;
+    }
 
     if ok {
         Ok(ExitCode::SUCCESS)
@@ -85,16 +210,187 @@ fn main() -> Result<ExitCode> {
     }
 }
 
-fn check_day<T>(day: &Day<T>, part_filter: Option<Part>, only: Option<Only>) -> Result<bool>
+/// Prints the day's puzzle statement, or the part's.
+fn show(day: &DayWrapper, part: Option<Part>) -> Result<()> {
+    let loc = day.source_file_location()?;
+    let markdown = read_data_file(&loc.dir, &loc.stem, "puzzle.md")?;
+    let Some(statement) = puzzle::statement(&markdown, part) else {
+        bail!("Part 2 of {} is not unlocked yet", day.titled_label()?);
+    };
+    println!("{}", puzzle::render(&statement));
+    Ok(())
+}
+
+fn dump_graph<T>(day: &Day<T>, path: &Path) -> Result<()> {
+    let export = day
+        .graph_export
+        .ok_or_else(|| anyhow!("No graph export for this day"))?;
+    let graph = export(&day.read_data_file("input")?)?;
+    let is_mermaid = path.extension().is_some_and(|ext| ext == "mmd");
+    let contents = if is_mermaid {
+        graph.to_mermaid()
+    } else {
+        graph.to_dot()
+    };
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Delay between the frames of animated images.
+const IMAGE_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// Writes the day's rendering to `path`, by default named after the label.
+fn dump_image(day: &DayWrapper, label: &str, path: Option<PathBuf>) -> Result<()> {
+    let frames = match day {
+        DayWrapper::I32(day) => render_image(day, label)?,
+        DayWrapper::U32(day) => render_image(day, label)?,
+        DayWrapper::U64(day) => render_image(day, label)?,
+        DayWrapper::Usize(day) => render_image(day, label)?,
+        DayWrapper::String(day) => render_image(day, label)?,
+    };
+    let last = frames
+        .last()
+        .ok_or_else(|| anyhow!("The {label} image has no frames"))?;
+    let path = path.unwrap_or_else(|| {
+        let extension = if frames.len() > 1 { "gif" } else { "png" };
+        PathBuf::from(format!("{label}.{extension}"))
+    });
+    let image = GridImage::new(|rgb: &Rgb| *rgb);
+    let file = BufWriter::new(File::create(&path)?);
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gif") => image.write_gif(&frames, IMAGE_FRAME_DELAY, file)?,
+        Some("svg") => image.write_svg(last, file)?,
+        _ => image.write_png(last, file)?,
+    }
+    println!(
+        "Wrote {label} image of {} to {}",
+        day.titled_label()?,
+        path.display()
+    );
+    Ok(())
+}
+
+fn render_image<T>(day: &Day<T>, label: &str) -> Result<Vec<Grid<Rgb>>> {
+    let export = day
+        .image_export(label)
+        .ok_or_else(|| anyhow!("No {label} image for this day"))?;
+    export(&day.read_data_file("input")?)
+}
+
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Polls the day's source and data files, and re-runs the checks (in a freshly built runner) on
+/// changes.
+fn watch(day: &DayWrapper) -> Result<()> {
+    let loc = day.source_file_location()?;
+    let source = Path::new("src")
+        .join(&loc.dir)
+        .join(format!("{}.rs", loc.stem));
+    let data_dir = Path::new("data").join(&loc.dir).join(&loc.stem);
+    // resolved before any rebuild replaces the executable
+    let runner = std::env::current_exe()?;
+    let report = std::env::temp_dir().join(format!("aoc-watch-{}.json", std::process::id()));
+    let runner_args = std::env::args()
+        .skip(1)
+        .filter(|arg| arg != "--watch")
+        .chain(["--report".to_owned(), report.display().to_string()])
+        .collect_vec();
+
+    let mut snapshot = None;
+    let mut previous_results = None;
+    loop {
+        let current = Snapshot::take(
+            std::slice::from_ref(&source),
+            std::slice::from_ref(&data_dir),
+        )?;
+        if snapshot.as_ref() != Some(&current) {
+            snapshot = Some(current);
+            println!("{}", format!("Checking {}", day.titled_label()?).bold());
+            if rebuild()? {
+                std::fs::remove_file(&report).ok(); // from the previous iteration, if any
+                Command::new(&runner).args(&runner_args).status()?;
+                let results = std::fs::read_to_string(&report)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|json| parse_report(&json));
+                match (&previous_results, results) {
+                    (_, Err(e)) => println!("{}", format!("No results: {e}").red()),
+                    (Some(previous), Ok(results)) => {
+                        let changes = watch::diff(previous, &results);
+                        if changes.is_empty() {
+                            println!("{}", "No answer changes".cyan());
+                        }
+                        for change in changes {
+                            println!("{}", change.cyan());
+                        }
+                        previous_results = Some(results);
+                    }
+                    (None, Ok(results)) => previous_results = Some(results),
+                }
+            }
+            println!("{}", "Watching for changes...".dimmed());
+        }
+        std::thread::sleep(WATCH_POLL_INTERVAL);
+    }
+}
+
+/// All cargo features of the crate (see `Cargo.toml`), and whether this build has them.
+const FEATURES: [(&str, bool); 4] = [
+    ("good_lp", cfg!(feature = "good_lp")),
+    ("previous-years", cfg!(feature = "previous-years")),
+    ("count-allocations", cfg!(feature = "count-allocations")),
+    ("tui", cfg!(feature = "tui")),
+];
+
+/// Features this build has, comma-separated.
+fn enabled_features() -> String {
+    FEATURES
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(feature, _)| feature)
+        .join(",")
+}
+
+/// Rebuild hook of the watch mode: builds the runner with the same profile and features.
+fn rebuild() -> Result<bool> {
+    let features = enabled_features();
+    let mut command = Command::new("cargo");
+    command.args([
+        "build",
+        "--quiet",
+        "--bin",
+        env!("CARGO_BIN_NAME"),
+        "--no-default-features",
+    ]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    if !features.is_empty() {
+        command.args(["--features", &features]);
+    }
+    let success = command.status()?.success();
+    if !success {
+        println!("{}", "Build failed".red().bold());
+    }
+    Ok(success)
+}
+
+fn check_day<T>(
+    day: &Day<T>,
+    part_filter: Option<Part>,
+    only: Option<Only>,
+    timings: &mut Timings,
+) -> Result<bool>
 where
     T: Eq + Debug,
 {
+    let check_example = only != Some(Only::Input);
+    let check_input = only != Some(Only::Example);
     let example_data = if day.distinct_examples {
         None
     } else {
-        Some(day.read_data_file("example")?)
+        Some(Data::read(day, "example", check_example, timings)?)
     };
-    let input_data = day.read_data_file("input")?;
+    let input_data = Data::read(day, "input", check_input, timings)?;
     let mut ok = true;
     for part in Part::iter() {
         if part_filter.is_some() && part != part_filter.unwrap() {
@@ -102,7 +398,7 @@ where
         }
         let part_example_data = if example_data.is_none() {
             let file_name = format!("example{}", part as u8);
-            Some(day.read_data_file(file_name.as_str())?)
+            Some(Data::read(day, file_name.as_str(), check_example, timings)?)
         } else {
             None
         };
@@ -112,77 +408,292 @@ where
             part_example_data
                 .as_ref()
                 .or(example_data.as_ref())
-                .unwrap()
-                .as_str(),
+                .unwrap(),
             &input_data,
             only,
+            timings,
         )?;
     }
     Ok(ok)
 }
 
+/// Data file contents, parsed up front (and only once) when the day has a parse step.
+struct Data {
+    text: String,
+    parsed: Option<(ParsedInput, Timing)>,
+    /// Whether the data is an example, solved with the day's example solvers
+    example: bool,
+}
+
+impl Data {
+    fn read<T>(day: &Day<T>, file_name: &str, parse: bool, timings: &Timings) -> Result<Self> {
+        let text = day.read_data_file(file_name)?;
+        let parsed = match &day.parse_step {
+            Some(step) if parse => Some(timings.measure(|| step.parse(&text))?),
+            _ => None,
+        };
+        let example = file_name.starts_with("example");
+        Ok(Self {
+            text,
+            parsed,
+            example,
+        })
+    }
+}
+
+/// Time spent solving, and parsing beforehand (shared by both parts) if the day has a parse step.
+#[derive(Debug, Copy, Clone)]
+struct Timing {
+    parse: Option<Duration>,
+    solve: Duration,
+    /// Heap usage
+    #[cfg(feature = "count-allocations")]
+    alloc: AllocStats,
+}
+
+impl Timing {
+    fn total(self) -> Duration {
+        self.solve + self.parse.unwrap_or_default()
+    }
+}
+
+/// Slowdowns smaller than this are measurement noise, whatever the threshold.
+const MIN_REGRESSION: Duration = Duration::from_millis(1);
+
+/// How durations are measured, and the history they are recorded to (and compared with).
+struct Timings {
+    repeat: u32,
+    /// Whether to print the solvers' diagnostics
+    verbose: bool,
+    /// Relative slowdown flagged as a regression, when comparing
+    regression_threshold: Option<f64>,
+    history: TimingHistory,
+    /// Profile, features and repetitions, which the durations are only comparable within
+    build: String,
+    /// Parts flagged as slower than their baseline
+    regressions: u32,
+    /// One entry per measured part, for `--report`
+    report: Vec<serde_json::Value>,
+}
+
+impl Timings {
+    /// Runs `f` `repeat` times, returning its last value and the median duration (and the heap
+    /// usage of the last run), and printing the diagnostics of the last run when verbose.
+    fn measure<V, F>(&self, mut f: F) -> Result<(V, Timing)>
+    where
+        F: FnMut() -> Result<V>,
+    {
+        let mut durations = vec![];
+        let mut value = None;
+        #[cfg(feature = "count-allocations")]
+        let mut alloc = AllocStats::default();
+        let mut diagnostics = vec![];
+        for _ in 0..self.repeat {
+            #[cfg(feature = "count-allocations")]
+            CountingAllocator::reset();
+            let start = Instant::now();
+            let result = if self.verbose {
+                let (result, run_diagnostics) = collect_diagnostics(&mut f);
+                diagnostics = run_diagnostics;
+                result
+            } else {
+                f()
+            };
+            value = Some(result?);
+            durations.push(start.elapsed());
+            #[cfg(feature = "count-allocations")]
+            {
+                alloc = CountingAllocator::stats();
+            }
+        }
+        for diagnostic in diagnostics {
+            eprintln!("{diagnostic}");
+        }
+        let value = value.ok_or_else(|| anyhow!("No repetitions"))?;
+        let timing = Timing {
+            parse: None,
+            solve: median(&mut durations),
+            #[cfg(feature = "count-allocations")]
+            alloc,
+        };
+        Ok((value, timing))
+    }
+
+    /// Records the timing (to the history and report, along with the answer), and checks it against
+    /// the baseline from the previous runs of the same build, counting regressions.
+    fn check<T>(
+        &mut self,
+        label: &str,
+        data_label: &str,
+        expected: Option<&T>,
+        actual: &T,
+        timing: Timing,
+    ) where
+        T: Eq + Debug,
+    {
+        let duration = timing.total();
+        #[cfg_attr(not(feature = "count-allocations"), allow(unused_mut))]
+        let mut entry = serde_json::json!({
+            "label": label,
+            "data": data_label,
+            "answer": format!("{actual:?}"),
+            "correct": expected.map(|expected| expected == actual),
+            "duration_ns": duration.as_nanos(),
+            "parse_ns": timing.parse.map(|parse| parse.as_nanos()),
+        });
+        #[cfg(feature = "count-allocations")]
+        {
+            entry["peak_bytes"] = timing.alloc.peak.into();
+            entry["allocated_bytes"] = timing.alloc.bytes.into();
+            entry["allocations"] = timing.alloc.allocations.into();
+        }
+        self.report.push(entry);
+        let key = format!("{label} {data_label} [{}]", self.build);
+        let baseline = self.history.baseline(&key);
+        self.history.record(&key, duration);
+        let (Some(threshold), Some(baseline)) = (self.regression_threshold, baseline) else {
+            return;
+        };
+        let slowdown = duration.as_secs_f64() / baseline.as_secs_f64() - 1.0;
+        if slowdown <= threshold || duration.saturating_sub(baseline) < MIN_REGRESSION {
+            return;
+        }
+        println!(
+            "{} {} {} [{} vs {} baseline, +{:.0}%]",
+            "SLOW".red().bold(),
+            label,
+            data_label,
+            format_duration(duration).red(),
+            format_duration(baseline).green(),
+            slowdown * 100.0
+        );
+        self.regressions += 1;
+    }
+}
+
 fn check_part<T>(
     day: &Day<T>,
     part: Part,
-    example_data: &str,
-    input_data: &str,
+    example_data: &Data,
+    input_data: &Data,
     only: Option<Only>,
+    timings: &mut Timings,
 ) -> Result<bool>
 where
     T: Eq + Debug,
 {
     let mut ok = true;
     if let Some((example_solution, input_solution)) = day.solutions(part) {
+        let label = day.label(part)?;
+        let titled_label = day.titled_label(part)?;
         if only != Some(Only::Input) {
+            let (actual, timing) = solve_part(day, part, example_data, timings)?;
             ok &= check_value(
-                day,
-                part,
+                &titled_label,
                 "example",
                 Some(example_solution),
-                solve_and_measure(day, part, example_data)?,
+                &actual,
+                timing,
             );
+            timings.check(&label, "example", Some(example_solution), &actual, timing);
+            ok &= check_variants(day, part, "example", &example_data.text, &actual, timings)?;
             if !ok {
                 return Ok(ok);
             }
         }
         if only != Some(Only::Example) {
+            let (actual, timing) = solve_part(day, part, input_data, timings)?;
             ok &= check_value(
-                day,
-                part,
+                &titled_label,
                 "input",
                 input_solution.as_ref(),
-                solve_and_measure(day, part, input_data)?,
+                &actual,
+                timing,
             );
+            timings.check(&label, "input", input_solution.as_ref(), &actual, timing);
+            ok &= check_variants(day, part, "input", &input_data.text, &actual, timings)?;
         }
     }
     Ok(ok)
 }
 
-fn solve_and_measure<T>(day: &Day<T>, part: Part, data: &str) -> Result<(T, Duration)> {
-    let solver = day.get_solver(part);
-    let start = Instant::now();
-    let value = solver(data)?;
-    let duration = start.elapsed();
-    Ok((value, duration))
+/// Checks that all variants of the part agree with the main solver's answer.
+fn check_variants<T>(
+    day: &Day<T>,
+    part: Part,
+    data_label: &str,
+    data: &str,
+    expected: &T,
+    timings: &mut Timings,
+) -> Result<bool>
+where
+    T: Eq + Debug,
+{
+    let mut ok = true;
+    for variant in day.variants(part) {
+        let (actual, timing) = timings.measure(|| (variant.solver)(data))?;
+        let label = day.variant_label(part, variant.name)?;
+        ok &= check_value(&label, data_label, Some(expected), &actual, timing);
+        timings.check(&label, data_label, Some(expected), &actual, timing);
+    }
+    Ok(ok)
+}
+
+/// Solves from the parsed input when available, from the raw data otherwise.
+fn solve_part<T>(day: &Day<T>, part: Part, data: &Data, timings: &Timings) -> Result<(T, Timing)> {
+    if let (Some(step), Some((input, parse))) = (&day.parse_step, &data.parsed) {
+        let (value, solve) = timings.measure(|| {
+            if data.example {
+                step.solve_example(part, input)
+            } else {
+                step.solve(part, input)
+            }
+        })?;
+        let timing = Timing {
+            parse: Some(parse.solve),
+            solve: solve.solve,
+            #[cfg(feature = "count-allocations")]
+            alloc: parse.alloc.then(solve.alloc),
+        };
+        return Ok((value, timing));
+    }
+    let solver = if data.example {
+        day.get_example_solver(part)
+    } else {
+        day.get_solver(part)
+    };
+    timings.measure(|| solver(&data.text))
 }
 
 fn check_value<T>(
-    day: &Day<T>,
-    part: Part,
     label: &str,
+    data_label: &str,
     expected: Option<&T>,
-    actual_result: (T, Duration),
+    actual: &T,
+    timing: Timing,
 ) -> bool
 where
     T: Eq + Debug,
 {
-    let (actual, duration) = actual_result;
+    let duration = timing.total();
     let duration_str = || {
-        let text = if duration.as_millis() >= 1 {
-            format!("{:.1} ms", duration.as_secs_f64() * 1e3)
-        } else {
-            format!("{:.1} µs", duration.as_secs_f64() * 1e6)
+        let text = match timing.parse {
+            Some(parse) => format!(
+                "{} (parse {} + solve {})",
+                format_duration(duration),
+                format_duration(parse),
+                format_duration(timing.solve)
+            ),
+            None => format_duration(duration),
         };
+        #[cfg(feature = "count-allocations")]
+        let text = format!(
+            "{}, peak {}, {} in {} allocs",
+            text,
+            format_bytes(timing.alloc.peak),
+            format_bytes(timing.alloc.bytes),
+            timing.alloc.allocations
+        );
         (if duration.as_millis() >= 50 {
             text.bold().truecolor(255, 83, 0) // orange
         } else {
@@ -191,7 +702,7 @@ where
         .to_string()
     };
     let (status, details, ok) = if let Some(expected) = expected {
-        if actual == *expected {
+        if actual == expected {
             ("OK".green(), duration_str(), true)
         } else {
             (
@@ -215,12 +726,52 @@ where
             true,
         )
     };
-    println!(
-        "{} {} {} [{}]",
-        status,
-        day.label(part).unwrap(),
-        label,
-        details
-    );
+    println!("{} {} {} [{}]", status, label, data_label, details);
     ok
 }
+
+fn format_duration(duration: Duration) -> String {
+    if duration.as_millis() >= 1 {
+        format!("{:.1} ms", duration.as_secs_f64() * 1e3)
+    } else {
+        format!("{:.1} µs", duration.as_secs_f64() * 1e6)
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+#[allow(clippy::cast_precision_loss)] // only for display
+fn format_bytes(bytes: usize) -> String {
+    const KIB: usize = 1 << 10;
+    const MIB: usize = 1 << 20;
+    if bytes >= MIB {
+        format!("{:.1} MiB", bytes as f64 / MIB as f64)
+    } else if bytes >= KIB {
+        format!("{:.1} KiB", bytes as f64 / KIB as f64)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn features_are_complete() {
+        let manifest = include_str!("../Cargo.toml");
+        let declared = manifest
+            .lines()
+            .skip_while(|line| *line != "[features]")
+            .skip(1)
+            .take_while(|line| !line.starts_with('['))
+            .filter_map(|line| line.split_once(" = "))
+            .map(|(feature, _)| feature)
+            .filter(|&feature| feature != "default");
+        for feature in declared {
+            assert!(
+                FEATURES.iter().any(|(name, _)| *name == feature),
+                "{feature} is missing from FEATURES"
+            );
+        }
+    }
+}
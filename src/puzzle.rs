@@ -0,0 +1,230 @@
+//! Puzzle statements, from the `puzzle.md` downloaded along with each day's input.
+
+use colored::{ColoredString, Colorize};
+use itertools::Itertools;
+
+use crate::challenge::Part;
+
+/// Heading of the second part, only there once the first part is solved.
+const PART2_HEADING: &str = r"\--- Part Two ---";
+
+/// Lines starting the page footer (answer form, input and sharing links), which is not part of the
+/// statement.
+const FOOTER_STARTS: [&str; 7] = [
+    "Answer:",
+    "Both parts of this puzzle are complete!",
+    "At this point, you should [return to your Advent calendar]",
+    "Although it hasn't changed, you can still [get your puzzle input]",
+    "If you still want to see it, you can [get your puzzle input]",
+    "To begin, [get your puzzle input]",
+    "You can also [Share",
+];
+
+/// The title from the first line (e.g. `Clumsy Crucible` from `\--- Day 17: Clumsy Crucible ---`).
+pub fn title(markdown: &str) -> Option<&str> {
+    let heading = markdown
+        .lines()
+        .next()?
+        .strip_prefix(r"\--- Day ")?
+        .strip_suffix(" ---")?;
+    Some(heading.split_once(": ")?.1)
+}
+
+/// The statement of the part (of both when unspecified) without the page footer, unless the part is
+/// not unlocked yet.
+pub fn statement(markdown: &str, part: Option<Part>) -> Option<String> {
+    let lines = markdown
+        .lines()
+        .take_while(|line| !FOOTER_STARTS.iter().any(|start| line.starts_with(start)))
+        .collect_vec();
+    let part2_start = lines.iter().position(|&line| line == PART2_HEADING);
+    let lines = match (part, part2_start) {
+        (Some(Part::Part1), Some(start)) => &lines[..start],
+        (Some(Part::Part2), Some(start)) => &lines[start..],
+        (Some(Part::Part2), None) => return None,
+        (None | Some(Part::Part1), _) => &lines[..],
+    };
+    Some(lines.join("\n").trim_end().to_owned())
+}
+
+/// Renders the markdown for the terminal: headings, emphasis and code are highlighted, while escapes
+/// and link targets are dropped.
+pub fn render(markdown: &str) -> String {
+    let mut in_code_block = false;
+    markdown
+        .lines()
+        .filter_map(|line| {
+            if line.starts_with("```") {
+                in_code_block = !in_code_block;
+                return None;
+            }
+            if in_code_block {
+                return Some(format!("    {}", line.cyan()));
+            }
+            // setext underline of the headings
+            if !line.is_empty() && line.chars().all(|c| c == '-') {
+                return None;
+            }
+            if line.starts_with(r"\---") {
+                return Some(line.replace('\\', "").green().bold().to_string());
+            }
+            Some(match line.strip_prefix("* ") {
+                Some(item) => format!("  • {}", render_inline(item)),
+                None => render_inline(line),
+            })
+        })
+        .join("\n")
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+struct Style {
+    emphasis: bool,
+    code: bool,
+    link: bool,
+}
+
+fn render_inline(line: &str) -> String {
+    let chars = line.chars().collect_vec();
+    let mut segments = vec![];
+    let mut text = String::new();
+    let mut style = Style::default();
+    let mut i = 0;
+    while i < chars.len() {
+        let rest = &chars[i + 1..];
+        match chars[i] {
+            '\\' if !style.code && !rest.is_empty() => {
+                text.push(rest[0]);
+                i += 1;
+            }
+            '`' => {
+                flush(&mut segments, &mut text, style);
+                style.code = !style.code;
+            }
+            '*' if style.emphasis => {
+                flush(&mut segments, &mut text, style);
+                style.emphasis = false;
+            }
+            // literal when it can't open an emphasis, e.g. `new = old * 5` in code
+            '*' if rest.first().is_some_and(|c| !c.is_whitespace()) && rest.contains(&'*') => {
+                flush(&mut segments, &mut text, style);
+                style.emphasis = true;
+            }
+            '[' if !style.code && !style.link && link_target_start(rest).is_some() => {
+                flush(&mut segments, &mut text, style);
+                style.link = true;
+            }
+            ']' if style.link && rest.first() == Some(&'(') => {
+                flush(&mut segments, &mut text, style);
+                style.link = false;
+                i += link_target_len(rest);
+            }
+            c => text.push(c),
+        }
+        i += 1;
+    }
+    flush(&mut segments, &mut text, style);
+    segments.iter().join("")
+}
+
+/// Position of the `(url)` closing a link text, if any.
+fn link_target_start(chars: &[char]) -> Option<usize> {
+    chars
+        .windows(2)
+        .position(|pair| pair == [']', '('])
+        .map(|position| position + 1)
+}
+
+/// Length of the `(url)` starting the chars, whose parentheses may be nested.
+fn link_target_len(chars: &[char]) -> usize {
+    let mut depth = 0;
+    for (i, c) in chars.iter().enumerate() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => return i + 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    chars.len()
+}
+
+fn flush(segments: &mut Vec<ColoredString>, text: &mut String, style: Style) {
+    if text.is_empty() {
+        return;
+    }
+    let mut segment = std::mem::take(text).normal();
+    if style.code {
+        segment = segment.cyan();
+    }
+    if style.emphasis {
+        segment = segment.bright_white().bold();
+    }
+    if style.link {
+        segment = segment.underline();
+    }
+    segments.push(segment);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = r"\--- Day 17: Clumsy Crucible ---
+----------
+
+The lava starts flowing *rapidly* once the [Lava Production Facility](/2023/day/15) starts up.
+
+```
+2413432311323
+3215453535623
+```
+
+* Each city block is marked by a `*single*` digit, e.g. `a * b`.
+
+Your puzzle answer was `1110`.
+
+\--- Part Two ---
+----------
+
+The crucibles of lava simply aren't large enough \*to provide\* lava.
+
+Answer:
+
+You can also [Shareon [Twitter](https://twitter.com/) [Mastodon](javascript:void(0);)] this puzzle.";
+
+    #[test]
+    fn puzzle_works() {
+        assert_eq!(title(PUZZLE), Some("Clumsy Crucible"));
+        assert_eq!(title("Puzzle"), None);
+
+        let part1 = statement(PUZZLE, Some(Part::Part1)).unwrap();
+        assert!(part1.ends_with("Your puzzle answer was `1110`."));
+        let part2 = statement(PUZZLE, Some(Part::Part2)).unwrap();
+        assert!(part2.starts_with(PART2_HEADING));
+        assert!(part2.ends_with("lava."));
+        assert_eq!(statement(&part1, Some(Part::Part2)), None);
+
+        colored::control::set_override(false);
+        let rendered = render(&statement(PUZZLE, None).unwrap());
+        colored::control::unset_override();
+        assert_eq!(
+            rendered.lines().collect_vec(),
+            [
+                "--- Day 17: Clumsy Crucible ---",
+                "",
+                "The lava starts flowing rapidly once the Lava Production Facility starts up.",
+                "",
+                "    2413432311323",
+                "    3215453535623",
+                "",
+                "  • Each city block is marked by a single digit, e.g. a * b.",
+                "",
+                "Your puzzle answer was 1110.",
+                "",
+                "--- Part Two ---",
+                "",
+                "The crucibles of lava simply aren't large enough *to provide* lava.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use indexmap::IndexMap;
+
+/// Number of recent runs kept per key, the baseline being their median.
+const KEPT_RUNS: usize = 10;
+
+/// Durations of previous runs, keyed by e.g. `year2024::day06::part2 input [release good_lp x1]`.
+///
+/// Stored as one `key<TAB>nanoseconds` line per run, oldest first.
+#[derive(Debug)]
+pub struct TimingHistory {
+    path: PathBuf,
+    runs: IndexMap<String, Vec<Duration>>,
+}
+
+impl TimingHistory {
+    /// Loads the history, which is empty if the file does not exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut history = Self {
+            path: path.to_path_buf(),
+            runs: IndexMap::new(),
+        };
+        if !path.exists() {
+            return Ok(history);
+        }
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read timing history {}", path.display()))?;
+        for line in contents.lines() {
+            let (key, nanos) = line
+                .split_once('\t')
+                .ok_or_else(|| anyhow!("Invalid timing history line: {line}"))?;
+            history.record(key, Duration::from_nanos(nanos.parse()?));
+        }
+        Ok(history)
+    }
+
+    /// Median of the recent runs, if there are any.
+    pub fn baseline(&self, key: &str) -> Option<Duration> {
+        let mut durations = self.runs.get(key)?.clone();
+        Some(median(&mut durations))
+    }
+
+    pub fn record(&mut self, key: &str, duration: Duration) {
+        let runs = self.runs.entry(key.to_owned()).or_default();
+        runs.push(duration);
+        if runs.len() > KEPT_RUNS {
+            runs.remove(0);
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let contents: String = self
+            .runs
+            .iter()
+            .flat_map(|(key, runs)| {
+                runs.iter()
+                    .map(move |d| format!("{key}\t{}\n", d.as_nanos()))
+            })
+            .collect();
+        std::fs::write(&self.path, contents)
+            .with_context(|| format!("Could not write timing history {}", self.path.display()))
+    }
+}
+
+/// # Panics
+///
+/// Will panic if there are no durations.
+pub fn median(durations: &mut [Duration]) -> Duration {
+    durations.sort_unstable();
+    durations[durations.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_works() {
+        let path = std::env::temp_dir().join(format!("timings-{}.tsv", std::process::id()));
+        let key = "year2024::day06::part2 input";
+        let mut history = TimingHistory::load(&path).unwrap();
+        assert_eq!(history.baseline(key), None);
+        for millis in 1..=15 {
+            history.record(key, Duration::from_millis(millis));
+        }
+        history.save().unwrap();
+
+        let history = TimingHistory::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        // runs 6 to 15 are kept
+        assert_eq!(history.baseline(key), Some(Duration::from_millis(11)));
+        assert_eq!(history.baseline("year2024::day06::part1 input"), None);
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Cycle in a sequence `x0, f(x0), f(f(x0)), ...`: the sequence enters the cycle at index `start`
+/// and then repeats every `period` steps.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Maps step `n` to the equivalent step within the first `start + period` steps.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Floyd's tortoise and hare: finds the cycle using constant memory.
+///
+/// Returns the cycle along with the first state of the cycle.
+pub fn floyd<T, F>(x0: &T, f: F) -> (Cycle, T)
+where
+    T: Clone + Eq,
+    F: Fn(&T) -> T,
+{
+    let mut tortoise = f(x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&f(&hare));
+    }
+
+    let mut start = 0;
+    tortoise = x0.clone();
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        period += 1;
+    }
+
+    (Cycle { start, period }, tortoise)
+}
+
+/// Brent's algorithm: finds the cycle using constant memory, with fewer evaluations of `f` than [`floyd`].
+///
+/// Returns the cycle along with the first state of the cycle.
+pub fn brent<T, F>(x0: &T, f: F) -> (Cycle, T)
+where
+    T: Clone + Eq,
+    F: Fn(&T) -> T,
+{
+    let (period, _) = brent_period(x0, &f);
+
+    let mut tortoise = x0.clone();
+    let mut hare = x0.clone();
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    (Cycle { start, period }, tortoise)
+}
+
+/// First phase of Brent's algorithm: finds the period, along with some state within the cycle.
+///
+/// Cheaper than [`brent`] when the start of the cycle is not needed.
+pub fn brent_period<T, F>(x0: &T, f: F) -> (usize, T)
+where
+    T: Clone + Eq,
+    F: Fn(&T) -> T,
+{
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(x0);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+    (period, hare)
+}
+
+/// Runs `step` from `x0` until a state with an already seen `key` comes up.
+///
+/// Returns the cycle along with all the states before the repetition (`start + period` states).
+pub fn find_repeat<T, K, F, G>(x0: T, mut step: F, mut key: G) -> (Cycle, Vec<T>)
+where
+    K: Hash + Eq,
+    F: FnMut(&T) -> T,
+    G: FnMut(&T) -> K,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut states = vec![x0];
+    loop {
+        let i = states.len() - 1;
+        let state = &states[i];
+        let state_key = key(state);
+        if let Some(&start) = seen.get(&state_key) {
+            states.pop();
+            let cycle = Cycle {
+                start,
+                period: i - start,
+            };
+            return (cycle, states);
+        }
+        seen.insert(state_key, i);
+        let next = step(state);
+        states.push(next);
+    }
+}
+
+/// Computes the state after `n` steps, extrapolating from the first repetition of a state `key`.
+pub fn nth_state<T, K, F, G>(x0: T, n: usize, step: F, key: G) -> T
+where
+    K: Hash + Eq,
+    F: FnMut(&T) -> T,
+    G: FnMut(&T) -> K,
+{
+    let (cycle, mut states) = find_repeat(x0, step, key);
+    states.swap_remove(cycle.reduce(n))
+}
+
+/// Detects the period of a sequence of observed values.
+pub struct PeriodDetector<T> {
+    history: Vec<T>,
+}
+
+impl<T> PeriodDetector<T>
+where
+    T: PartialEq,
+{
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            history: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.history.push(value);
+    }
+
+    /// Smallest period `p` such that `history[i] == history[i + p]` for all `i`, if the history
+    /// contains at least two full periods.
+    ///
+    /// Computed in linear time using the KMP failure function.
+    pub fn period(&self) -> Option<usize> {
+        let n = self.history.len();
+        let mut failure = vec![0; n];
+        for i in 1..n {
+            let mut k = failure[i - 1];
+            while k > 0 && self.history[i] != self.history[k] {
+                k = failure[k - 1];
+            }
+            if self.history[i] == self.history[k] {
+                k += 1;
+            }
+            failure[i] = k;
+        }
+        let period = n - failure.last()?;
+        (period <= n / 2).then_some(period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn step(x: &u32) -> u32 {
+        if *x == 5 { 2 } else { x + 1 }
+    }
+
+    #[test]
+    fn floyd_and_brent_work() {
+        let expected = (
+            Cycle {
+                start: 2,
+                period: 4,
+            },
+            2,
+        );
+        assert_eq!(floyd(&0, step), expected);
+        assert_eq!(brent(&0, step), expected);
+        assert_eq!(brent_period(&0, step).0, 4);
+        assert_eq!(
+            brent(&7, |_| 7),
+            (
+                Cycle {
+                    start: 0,
+                    period: 1
+                },
+                7
+            )
+        );
+    }
+
+    #[test]
+    fn nth_state_works() {
+        assert_eq!(nth_state(0, 1, step, |x| *x), 1);
+        assert_eq!(nth_state(0, 5, step, |x| *x), 5);
+        assert_eq!(nth_state(0, 1_000_000_000, step, |x| *x), 4);
+    }
+
+    #[test]
+    fn period_detector_works() {
+        let mut detector = PeriodDetector::with_capacity(1);
+        for b in [0, 0, 1, 1, 1, 0, 0, 0, 1, 1, 1, 0] {
+            detector.push(b);
+        }
+        assert_eq!(detector.period(), Some(6));
+
+        let mut detector = PeriodDetector::with_capacity(1);
+        for b in [0, 1, 2, 0, 1] {
+            detector.push(b);
+        }
+        assert_eq!(detector.period(), None);
+    }
+}
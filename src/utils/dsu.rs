@@ -0,0 +1,172 @@
+/// Disjoint-set union (union-find), with union by size and path halving.
+#[derive(Debug, Clone)]
+pub struct Dsu {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl Dsu {
+    /// `n` singleton components.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Representative of the component of `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut x = x;
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the components of `a` and `b`, returning whether they were distinct.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (big, small) = if self.size[a] >= self.size[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        self.components -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the component of `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.components <= 1
+    }
+
+    /// Sizes of all components, in no particular order.
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|root| self.size[root])
+    }
+
+    /// Sizes of the `k` largest components, in decreasing order.
+    pub fn largest_components(&self, k: usize) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self.component_sizes().collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes.truncate(k);
+        sizes
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct KruskalEdge<W> {
+    pub a: usize,
+    pub b: usize,
+    pub weight: W,
+    /// Whether the edge merged two components (i.e. belongs to the minimum spanning forest).
+    pub merged: bool,
+    /// Whether all nodes are connected after this edge.
+    pub connected: bool,
+}
+
+/// Kruskal's algorithm, processing edges `(a, b, weight)` one at a time.
+///
+/// Edges must be given in increasing order of weight: they can come from a sorted list, or be
+/// streamed lazily. The union-find state is available between steps.
+#[derive(Debug, Clone)]
+pub struct Kruskal<I> {
+    dsu: Dsu,
+    edges: I,
+}
+
+impl<I, W> Kruskal<I>
+where
+    I: Iterator<Item = (usize, usize, W)>,
+{
+    pub fn new<E: IntoIterator<IntoIter = I>>(n: usize, edges: E) -> Self {
+        Self {
+            dsu: Dsu::new(n),
+            edges: edges.into_iter(),
+        }
+    }
+
+    pub fn dsu(&self) -> &Dsu {
+        &self.dsu
+    }
+}
+
+impl<I, W> Iterator for Kruskal<I>
+where
+    I: Iterator<Item = (usize, usize, W)>,
+{
+    type Item = KruskalEdge<W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (a, b, weight) = self.edges.next()?;
+        let merged = self.dsu.union(a, b);
+        Some(KruskalEdge {
+            a,
+            b,
+            weight,
+            merged,
+            connected: self.dsu.is_connected(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dsu_works() {
+        let mut dsu = Dsu::new(6);
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 1));
+        assert!(!dsu.union(0, 2));
+        assert!(dsu.union(3, 4));
+        assert!(dsu.same(0, 2) && !dsu.same(0, 3));
+        assert_eq!(dsu.size_of(1), 3);
+        assert_eq!(dsu.component_count(), 3);
+        assert_eq!(dsu.largest_components(2), vec![3, 2]);
+        assert_eq!(dsu.component_sizes().sum::<usize>(), 6);
+    }
+
+    #[test]
+    fn kruskal_works() {
+        let edges = [(0, 1, 1), (1, 2, 2), (0, 2, 3), (2, 3, 4), (1, 3, 5)];
+        let mut kruskal = Kruskal::new(4, edges);
+        let mst: Vec<_> = kruskal.by_ref().take(3).filter(|e| e.merged).collect();
+        assert_eq!(mst.iter().map(|e| e.weight).sum::<i32>(), 3);
+        assert_eq!(kruskal.dsu().largest_components(3), vec![3, 1]);
+        let last = kruskal.find(|e| e.connected).unwrap();
+        assert_eq!((last.a, last.b), (2, 3));
+    }
+}
@@ -0,0 +1,350 @@
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+use crate::utils::grid::{Coord, Direction, Grid};
+use crate::utils::math::gcd;
+
+/// Lattice point `(x, y)`, with y pointing down like in [`Grid`].
+pub type Point2 = (i64, i64);
+
+/// Simple polygon with lattice points as vertices.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Polygon {
+    vertices: Vec<Point2>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point2>) -> Self {
+        Self { vertices }
+    }
+
+    /// Polygon traced by walking the given steps from the origin.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the steps do not lead back to the origin.
+    pub fn from_steps<I: IntoIterator<Item = (Direction, i64)>>(steps: I) -> Self {
+        let mut vertices = Vec::new();
+        let mut pos = (0, 0);
+        for (dir, len) in steps {
+            vertices.push(pos);
+            let (x, y) = pos;
+            pos = match dir {
+                Direction::N => (x, y - len),
+                Direction::S => (x, y + len),
+                Direction::W => (x - len, y),
+                Direction::E => (x + len, y),
+            };
+        }
+        assert_eq!(pos, (0, 0), "Steps do not form a closed loop");
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Point2] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (Point2, Point2)> + '_ {
+        self.vertices.iter().copied().circular_tuple_windows()
+    }
+
+    pub fn is_rectilinear(&self) -> bool {
+        self.edges().all(|(a, b)| a.0 == b.0 || a.1 == b.1)
+    }
+
+    /// Twice the signed area (shoelace formula), positive for counter-clockwise vertices in a
+    /// y-up frame.
+    pub fn signed_double_area(&self) -> i64 {
+        self.edges().map(|(a, b)| a.0 * b.1 - a.1 * b.0).sum()
+    }
+
+    pub fn double_area(&self) -> u64 {
+        self.signed_double_area().unsigned_abs()
+    }
+
+    /// Number of lattice points on the boundary.
+    pub fn boundary_points(&self) -> u64 {
+        self.edges()
+            .map(|(a, b)| gcd(a.0.abs_diff(b.0), a.1.abs_diff(b.1)))
+            .sum()
+    }
+
+    /// Number of lattice points strictly inside, using Pick's theorem `A = I + B/2 - 1`.
+    pub fn interior_points(&self) -> u64 {
+        (self.double_area() + 2 - self.boundary_points()) / 2
+    }
+
+    /// Number of lattice points inside or on the boundary.
+    pub fn lattice_points(&self) -> u64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    pub fn on_boundary(&self, p: Point2) -> bool {
+        self.edges().any(|(a, b)| {
+            let cross = (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+            cross == 0
+                && a.0.min(b.0) <= p.0
+                && p.0 <= a.0.max(b.0)
+                && a.1.min(b.1) <= p.1
+                && p.1 <= a.1.max(b.1)
+        })
+    }
+
+    /// Whether `p` is inside the polygon or on its boundary.
+    pub fn contains(&self, p: Point2) -> bool {
+        if self.on_boundary(p) {
+            return true;
+        }
+        // ray casting towards +x, counting edges crossing the horizontal line through `p`
+        let crossings = self
+            .edges()
+            .filter(|(a, b)| (a.1 > p.1) != (b.1 > p.1))
+            .filter(|(a, b)| {
+                // x of the intersection is greater than p.x, without dividing
+                let lhs = (p.0 - a.0) * (b.1 - a.1);
+                let rhs = (b.0 - a.0) * (p.1 - a.1);
+                if b.1 > a.1 { lhs < rhs } else { lhs > rhs }
+            })
+            .count();
+        crossings % 2 == 1
+    }
+}
+
+/// Rectilinear polygon on a compressed grid, answering "are all the lattice points of this
+/// rectangle inside" queries in constant time.
+///
+/// Each distinct vertex coordinate becomes a grid line, and each gap between two consecutive
+/// coordinates becomes a single cell, so the grid size only depends on the number of vertices.
+/// The outside is flooded through all the cells (a 1-wide corridor between two edges still leads
+/// somewhere), but only the cells holding lattice points count, so a 1-wide notch between two
+/// edges does not split the rectangles spanning it.
+#[derive(Debug, Clone)]
+pub struct RectilinearRegion {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    // number of outside cells with lattice points in the compressed grid, above and left of each
+    // cell
+    outside_prefix: Grid<usize>,
+}
+
+impl RectilinearRegion {
+    /// # Panics
+    ///
+    /// Will panic if the polygon is not rectilinear.
+    pub fn new(polygon: &Polygon) -> Self {
+        assert!(polygon.is_rectilinear(), "Polygon is not rectilinear");
+        let xs = polygon
+            .vertices
+            .iter()
+            .map(|p| p.0)
+            .sorted()
+            .dedup()
+            .collect_vec();
+        let ys = polygon
+            .vertices
+            .iter()
+            .map(|p| p.1)
+            .sorted()
+            .dedup()
+            .collect_vec();
+
+        // compressed cells, with a one-cell margin around the polygon
+        let (w, h) = (2 * xs.len() + 1, 2 * ys.len() + 1);
+        let mut cells = Grid::empty(w, h, Cell::Unknown);
+        for (a, b) in polygon.edges() {
+            let (x1, x2) = (compress(&xs, a.0).unwrap(), compress(&xs, b.0).unwrap());
+            let (y1, y2) = (compress(&ys, a.1).unwrap(), compress(&ys, b.1).unwrap());
+            for x in x1.min(x2)..=x1.max(x2) {
+                for y in y1.min(y2)..=y1.max(y2) {
+                    cells.set(&Coord(x, y), Cell::Boundary);
+                }
+            }
+        }
+        let mut queue = vec![Coord(0, 0)];
+        cells.set(&Coord(0, 0), Cell::Outside);
+        while let Some(coord) = queue.pop() {
+            for dir in Direction::iter() {
+                let Some(next) = cells.walk(&coord, dir) else {
+                    continue;
+                };
+                if *cells.get(&next) == Cell::Unknown {
+                    cells.set(&next, Cell::Outside);
+                    queue.push(next);
+                }
+            }
+        }
+
+        let mut outside_prefix = Grid::empty(w + 1, h + 1, 0);
+        for (Coord(x, y), cell) in &cells {
+            let lattice = has_lattice_points(&xs, x) && has_lattice_points(&ys, y);
+            let count = usize::from(cell == Cell::Outside && lattice)
+                + outside_prefix.get(&Coord(x, y + 1))
+                + outside_prefix.get(&Coord(x + 1, y))
+                - outside_prefix.get(&Coord(x, y));
+            outside_prefix.set(&Coord(x + 1, y + 1), count);
+        }
+
+        Self {
+            xs,
+            ys,
+            outside_prefix,
+        }
+    }
+
+    /// Whether the rectangle with opposite corners `a` and `b` lies entirely within the polygon
+    /// (boundary included).
+    pub fn contains_rect(&self, a: Point2, b: Point2) -> bool {
+        let (Some(x1), Some(x2), Some(y1), Some(y2)) = (
+            compress(&self.xs, a.0.min(b.0)),
+            compress(&self.xs, a.0.max(b.0)),
+            compress(&self.ys, a.1.min(b.1)),
+            compress(&self.ys, a.1.max(b.1)),
+        ) else {
+            return false;
+        };
+        let prefix = |x, y| *self.outside_prefix.get(&Coord(x, y));
+        prefix(x2 + 1, y2 + 1) + prefix(x1, y1) - prefix(x1, y2 + 1) - prefix(x2 + 1, y1) == 0
+    }
+
+    pub fn contains(&self, p: Point2) -> bool {
+        self.contains_rect(p, p)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Cell {
+    Unknown,
+    Boundary,
+    Outside,
+}
+
+/// Index of the compressed cell containing `v`: odd indices are the lines `coords[i]`, even
+/// indices the gaps between them (including the margin on both sides).
+fn compress(coords: &[i64], v: i64) -> Option<usize> {
+    let i = coords.partition_point(|&c| c < v);
+    match coords.get(i) {
+        Some(&c) if c == v => Some(2 * i + 1),
+        Some(_) if i > 0 => Some(2 * i),
+        _ => None,
+    }
+}
+
+/// Whether the compressed cell `i` (see [`compress`]) holds lattice points, which gaps between
+/// consecutive coordinates do not.
+fn has_lattice_points(coords: &[i64], i: usize) -> bool {
+    i % 2 == 1 || i == 0 || i == 2 * coords.len() || coords[i / 2] - coords[i / 2 - 1] > 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polygon_works() {
+        use Direction::*;
+        // 4x2 rectangle with a 2x1 bump on its top edge
+        let polygon = Polygon::from_steps([
+            (E, 1),
+            (N, 1),
+            (E, 2),
+            (S, 1),
+            (E, 1),
+            (S, 2),
+            (W, 4),
+            (N, 2),
+        ]);
+        assert_eq!(polygon.vertices().len(), 8);
+        assert!(polygon.is_rectilinear());
+        assert_eq!(polygon.double_area(), 2 * 10);
+        assert_eq!(polygon.boundary_points(), 14);
+        assert_eq!(polygon.interior_points(), 4);
+        assert_eq!(polygon.lattice_points(), 18);
+        assert!(polygon.contains((2, 1)) && polygon.contains((2, -1)) && polygon.contains((0, 0)));
+        assert!(!polygon.contains((2, -2)) && !polygon.contains((5, 1)));
+
+        let triangle = Polygon::new(vec![(0, 0), (4, 0), (0, 4)]);
+        assert_eq!(triangle.double_area(), 16);
+        assert_eq!(triangle.lattice_points(), 15);
+        assert!(
+            triangle.contains((1, 1)) && triangle.contains((2, 2)) && !triangle.contains((3, 2))
+        );
+    }
+
+    #[test]
+    fn rectilinear_region_works() {
+        // U shape
+        let polygon = Polygon::new(vec![
+            (0, 0),
+            (2, 0),
+            (2, 4),
+            (4, 4),
+            (4, 0),
+            (6, 0),
+            (6, 6),
+            (0, 6),
+        ]);
+        let region = RectilinearRegion::new(&polygon);
+        assert!(region.contains_rect((0, 0), (2, 6)));
+        assert!(region.contains_rect((0, 4), (6, 6)));
+        assert!(region.contains_rect((1, 5), (5, 6)));
+        assert!(!region.contains_rect((0, 0), (6, 6)));
+        assert!(!region.contains_rect((1, 3), (5, 5)));
+        assert!(!region.contains_rect((0, 0), (7, 1)));
+        assert!(region.contains_rect((2, 0), (2, 4)) && !region.contains_rect((3, 0), (3, 4)));
+        for x in -1..=7 {
+            for y in -1..=7 {
+                assert_eq!(
+                    region.contains((x, y)),
+                    polygon.contains((x, y)),
+                    "({x}, {y})"
+                );
+            }
+        }
+    }
+    #[test]
+    fn rectilinear_region_works_at_lattice_resolution() {
+        // 1-wide notch, without lattice points inside
+        let notch = Polygon::new(vec![
+            (0, 0),
+            (2, 0),
+            (2, 4),
+            (3, 4),
+            (3, 0),
+            (5, 0),
+            (5, 6),
+            (0, 6),
+        ]);
+        let region = RectilinearRegion::new(&notch);
+        assert!(region.contains_rect((0, 0), (5, 6)));
+        assert!(!region.contains_rect((0, -1), (5, 6)));
+
+        // room (outside) only reached through a 1-wide corridor
+        let corridor = Polygon::new(vec![
+            (0, 0),
+            (10, 0),
+            (10, 10),
+            (0, 10),
+            (0, 6),
+            (4, 6),
+            (4, 8),
+            (8, 8),
+            (8, 2),
+            (4, 2),
+            (4, 5),
+            (0, 5),
+        ]);
+        let region = RectilinearRegion::new(&corridor);
+        assert!(region.contains_rect((0, 0), (10, 2)));
+        assert!(region.contains_rect((0, 0), (4, 10)));
+        assert!(!region.contains_rect((4, 2), (8, 8)));
+        for x in -1..=11 {
+            for y in -1..=11 {
+                assert_eq!(
+                    region.contains((x, y)),
+                    corridor.contains((x, y)),
+                    "({x}, {y})"
+                );
+            }
+        }
+    }
+}
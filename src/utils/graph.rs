@@ -0,0 +1,377 @@
+use indexmap::IndexSet;
+
+pub type NodeId = u32;
+
+/// Maps names to dense ids, in insertion order.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    names: IndexSet<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if there are more than `u32::MAX` names.
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        let idx = match self.names.get_index_of(name) {
+            Some(idx) => idx,
+            None => self.names.insert_full(name.to_owned()).0,
+        };
+        NodeId::try_from(idx).unwrap()
+    }
+
+    #[allow(clippy::cast_possible_truncation)] // checked on insertion
+    pub fn get(&self, name: &str) -> Option<NodeId> {
+        self.names.get_index_of(name).map(|idx| idx as NodeId)
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if `id` was not returned by this interner.
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Directed graph with named nodes, keeping both outgoing and incoming edges.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Interner,
+    successors: Vec<Vec<NodeId>>,
+    predecessors: Vec<Vec<NodeId>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, name: &str) -> NodeId {
+        let id = self.names.intern(name);
+        if self.successors.len() < self.names.len() {
+            self.successors.push(vec![]);
+            self.predecessors.push(vec![]);
+        }
+        id
+    }
+
+    pub fn add_edge(&mut self, from: &str, to: &str) -> (NodeId, NodeId) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.successors[from as usize].push(to);
+        self.predecessors[to as usize].push(from);
+        (from, to)
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.names.get(name)
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        self.names.name(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if there are more than `u32::MAX` nodes.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> + use<> {
+        0..NodeId::try_from(self.len()).unwrap()
+    }
+
+    pub fn successors(&self, id: NodeId) -> &[NodeId] {
+        &self.successors[id as usize]
+    }
+
+    pub fn predecessors(&self, id: NodeId) -> &[NodeId] {
+        &self.predecessors[id as usize]
+    }
+
+    /// Nodes ordered so that every edge goes forward (Kahn's algorithm), or `None` if the graph
+    /// has a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        self.topological_sort_of(&vec![true; self.len()])
+    }
+
+    /// Strongly connected components (Kosaraju's algorithm), in topological order of the
+    /// condensed graph.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        // first pass: nodes by increasing DFS finish time
+        let mut finished = Vec::with_capacity(self.len());
+        let mut visited = vec![false; self.len()];
+        for root in self.nodes() {
+            if visited[root as usize] {
+                continue;
+            }
+            visited[root as usize] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some((node, next_idx)) = stack.pop() {
+                if let Some(&next) = self.successors(node).get(next_idx) {
+                    stack.push((node, next_idx + 1));
+                    if !visited[next as usize] {
+                        visited[next as usize] = true;
+                        stack.push((next, 0));
+                    }
+                } else {
+                    finished.push(node);
+                }
+            }
+        }
+
+        // second pass: components are what is reachable backwards, by decreasing finish time
+        let mut components = Vec::new();
+        let mut assigned = vec![false; self.len()];
+        for &root in finished.iter().rev() {
+            if assigned[root as usize] {
+                continue;
+            }
+            assigned[root as usize] = true;
+            let mut component = vec![root];
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                for &prev in self.predecessors(node) {
+                    if !assigned[prev as usize] {
+                        assigned[prev as usize] = true;
+                        component.push(prev);
+                        stack.push(prev);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Nodes reachable from `start` (including itself), indexed by id.
+    pub fn reachable_from(&self, start: NodeId) -> Vec<bool> {
+        self.flood(start, Self::successors)
+    }
+
+    /// Nodes from which `target` is reachable (including itself), indexed by id.
+    pub fn reaching(&self, target: NodeId) -> Vec<bool> {
+        self.flood(target, Self::predecessors)
+    }
+
+    /// Number of distinct paths from `from` to `to`, or `None` if there are infinitely many
+    /// (i.e. a cycle lies on such a path) or too many to fit a `u64`.
+    pub fn count_paths(&self, from: NodeId, to: NodeId) -> Option<u64> {
+        let relevant = self.between(from, to);
+        if !relevant[from as usize] {
+            return Some(0);
+        }
+        let order = self.topological_sort_of(&relevant)?;
+        let mut counts = vec![0; self.len()];
+        counts[from as usize] = 1;
+        for node in order {
+            let count = counts[node as usize];
+            for &next in self.successors(node) {
+                counts[next as usize] = u64::checked_add(counts[next as usize], count)?;
+            }
+        }
+        Some(counts[to as usize])
+    }
+
+    /// Number of distinct paths from `from` to `to` visiting every node of `must_visit`, or
+    /// `None` if a cycle lies on a path from `from` to `to` or there are too many paths to fit a
+    /// `u64`.
+    pub fn count_paths_via(&self, from: NodeId, to: NodeId, must_visit: &[NodeId]) -> Option<u64> {
+        let relevant = self.between(from, to);
+        if !must_visit.iter().all(|&node| relevant[node as usize]) {
+            return Some(0);
+        }
+        // without cycles, a path visits nodes in topological order: count each segment separately
+        let order = self.topological_sort_of(&relevant)?;
+        let mut rank = vec![0; self.len()];
+        for (i, &node) in order.iter().enumerate() {
+            rank[node as usize] = i;
+        }
+        let mut stops = must_visit.to_vec();
+        stops.sort_unstable_by_key(|&node| rank[node as usize]);
+        stops.dedup();
+
+        let waypoints = [from]
+            .into_iter()
+            .chain(stops)
+            .chain([to])
+            .collect::<Vec<_>>();
+        waypoints
+            .windows(2)
+            .map(|w| self.count_paths(w[0], w[1]))
+            .try_fold(1, |product, count| u64::checked_mul(product, count?))
+    }
+
+    /// Nodes on a path from `from` to `to`, indexed by id.
+    fn between(&self, from: NodeId, to: NodeId) -> Vec<bool> {
+        let reaching = self.reaching(to);
+        self.reachable_from(from)
+            .into_iter()
+            .zip(reaching)
+            .map(|(a, b)| a && b)
+            .collect()
+    }
+
+    fn flood(&self, start: NodeId, neighbors: fn(&Self, NodeId) -> &[NodeId]) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        seen[start as usize] = true;
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for &next in neighbors(self, node) {
+                if !seen[next as usize] {
+                    seen[next as usize] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Topological order of the subgraph induced by the `included` nodes.
+    fn topological_sort_of(&self, included: &[bool]) -> Option<Vec<NodeId>> {
+        let mut in_degrees = vec![0; self.len()];
+        for node in self.nodes().filter(|&n| included[n as usize]) {
+            for &next in self.successors(node) {
+                in_degrees[next as usize] += 1;
+            }
+        }
+        let mut queue = self
+            .nodes()
+            .filter(|&n| included[n as usize] && in_degrees[n as usize] == 0)
+            .collect::<Vec<_>>();
+        let mut order = Vec::new();
+        while let Some(node) = queue.pop() {
+            order.push(node);
+            for &next in self.successors(node) {
+                in_degrees[next as usize] -= 1;
+                if in_degrees[next as usize] == 0 && included[next as usize] {
+                    queue.push(next);
+                }
+            }
+        }
+        (order.len() == included.iter().filter(|&&b| b).count()).then_some(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    #[test]
+    fn dag_works() {
+        let g = graph(&[
+            ("a", "b"),
+            ("a", "c"),
+            ("b", "d"),
+            ("c", "d"),
+            ("d", "e"),
+            ("b", "e"),
+            ("x", "e"),
+        ]);
+        let id = |name| g.id(name).unwrap();
+        assert_eq!(g.len(), 6);
+        assert_eq!(g.name(id("d")), "d");
+        assert_eq!(g.predecessors(id("e")).len(), 3);
+
+        let order = g.topological_sort().unwrap();
+        for node in g.nodes() {
+            let pos = |n| order.iter().position(|&o| o == n).unwrap();
+            assert!(g.successors(node).iter().all(|&next| pos(node) < pos(next)));
+        }
+
+        assert_eq!(g.count_paths(id("a"), id("e")), Some(3));
+        assert_eq!(g.count_paths(id("a"), id("x")), Some(0));
+        assert_eq!(g.count_paths_via(id("a"), id("e"), &[id("d")]), Some(2));
+        assert_eq!(
+            g.count_paths_via(id("a"), id("e"), &[id("d"), id("b")]),
+            Some(1)
+        );
+        assert_eq!(
+            g.count_paths_via(id("a"), id("e"), &[id("b"), id("c")]),
+            Some(0)
+        );
+        assert_eq!(g.reaching(id("d")).iter().filter(|&&b| b).count(), 4);
+    }
+
+    #[test]
+    fn path_counts_overflow_to_none() {
+        // 2^64 paths through 64 diamonds
+        let mut edges = vec![];
+        let names = (0..=64).map(|i| format!("n{i}")).collect::<Vec<_>>();
+        let (lefts, rights) = (
+            (0..64).map(|i| format!("l{i}")).collect::<Vec<_>>(),
+            (0..64).map(|i| format!("r{i}")).collect::<Vec<_>>(),
+        );
+        for i in 0..64 {
+            for side in [&lefts[i], &rights[i]] {
+                edges.push((names[i].as_str(), side.as_str()));
+                edges.push((side.as_str(), names[i + 1].as_str()));
+            }
+        }
+        let g = graph(&edges);
+        let id = |name| g.id(name).unwrap();
+        assert_eq!(g.count_paths(id("n0"), id("n63")), Some(1 << 63));
+        assert_eq!(g.count_paths(id("n0"), id("n64")), None);
+        assert_eq!(g.count_paths_via(id("n0"), id("n64"), &[id("n32")]), None);
+    }
+
+    #[test]
+    fn cycles_work() {
+        let g = graph(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "d"),
+            ("f", "a"),
+        ]);
+        let id = |name| g.id(name).unwrap();
+        assert_eq!(g.topological_sort(), None);
+        let components = g
+            .strongly_connected_components()
+            .into_iter()
+            .map(|mut c| {
+                c.sort_unstable();
+                c.into_iter().map(|n| g.name(n)).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            components,
+            vec![vec!["f"], vec!["a", "b", "c"], vec!["d", "e"]]
+        );
+        assert_eq!(g.count_paths(id("f"), id("d")), None);
+        assert_eq!(g.count_paths(id("d"), id("f")), Some(0));
+        assert!(g.reachable_from(id("d")).iter().filter(|&&b| b).count() == 2);
+
+        // the cycle is out of the way
+        let g = graph(&[("a", "b"), ("b", "c"), ("c", "b"), ("a", "d")]);
+        let id = |name| g.id(name).unwrap();
+        assert_eq!(g.count_paths_via(id("a"), id("d"), &[]), Some(1));
+        assert_eq!(g.count_paths_via(id("a"), id("d"), &[id("b")]), Some(0));
+    }
+}
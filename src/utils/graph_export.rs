@@ -0,0 +1,208 @@
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+
+use crate::utils::graph::Graph;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Shape {
+    #[default]
+    Ellipse,
+    Box,
+    Circle,
+    DoubleCircle,
+    Diamond,
+    Hexagon,
+}
+
+impl Shape {
+    fn dot(self) -> &'static str {
+        match self {
+            Shape::Ellipse => "ellipse",
+            Shape::Box => "box",
+            Shape::Circle => "circle",
+            Shape::DoubleCircle => "doublecircle",
+            Shape::Diamond => "diamond",
+            Shape::Hexagon => "hexagon",
+        }
+    }
+
+    fn mermaid(self) -> (&'static str, &'static str) {
+        match self {
+            Shape::Ellipse => ("(", ")"),
+            Shape::Box => ("[", "]"),
+            Shape::Circle => ("((", "))"),
+            Shape::DoubleCircle => ("(((", ")))"),
+            Shape::Diamond => ("{", "}"),
+            Shape::Hexagon => ("{{", "}}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    label: Option<String>,
+    shape: Shape,
+}
+
+#[derive(Debug, Clone)]
+struct Edge {
+    from: usize, // node index
+    to: usize,
+    label: Option<String>,
+}
+
+/// Directed graph description, to be rendered as Graphviz DOT or Mermaid.
+///
+/// Nodes referenced by edges but never declared are rendered with the default shape.
+#[derive(Debug, Clone, Default)]
+pub struct GraphExport {
+    nodes: IndexMap<String, Node>,
+    edges: Vec<Edge>,
+}
+
+impl GraphExport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn node(&mut self, id: &str, shape: Shape) -> &mut Self {
+        self.nodes.entry(id.to_owned()).or_default().shape = shape;
+        self
+    }
+
+    pub fn node_with_label(&mut self, id: &str, shape: Shape, label: &str) -> &mut Self {
+        self.nodes.insert(
+            id.to_owned(),
+            Node {
+                label: Some(label.to_owned()),
+                shape,
+            },
+        );
+        self
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str) -> &mut Self {
+        self.add_edge(from, to, None)
+    }
+
+    pub fn edge_with_label(&mut self, from: &str, to: &str, label: &str) -> &mut Self {
+        self.add_edge(from, to, Some(label.to_owned()))
+    }
+
+    fn add_edge(&mut self, from: &str, to: &str, label: Option<String>) -> &mut Self {
+        let [from, to] = [from, to].map(|id| {
+            let entry = self.nodes.entry(id.to_owned());
+            let idx = entry.index();
+            entry.or_default();
+            idx
+        });
+        self.edges.push(Edge { from, to, label });
+        self
+    }
+
+    pub fn to_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let ids = self.nodes.keys().collect_vec();
+        let mut out = String::from("digraph {\n");
+        for (id, node) in &self.nodes {
+            let label = node.label.as_deref().unwrap_or(id);
+            writeln!(
+                out,
+                "    {} [label={}, shape={}];",
+                quote(id),
+                quote(label),
+                node.shape.dot()
+            )
+            .unwrap();
+        }
+        for edge in &self.edges {
+            write!(
+                out,
+                "    {} -> {}",
+                quote(ids[edge.from]),
+                quote(ids[edge.to])
+            )
+            .unwrap();
+            if let Some(label) = &edge.label {
+                write!(out, " [label={}]", quote(label)).unwrap();
+            }
+            out.push_str(";\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Mermaid flowchart. Node ids are replaced by `n<index>`, as some names are reserved words.
+    pub fn to_mermaid(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('"', "#quot;"));
+        let mut out = String::from("flowchart LR\n");
+        for (idx, (id, node)) in self.nodes.iter().enumerate() {
+            let label = node.label.as_deref().unwrap_or(id);
+            let (open, close) = node.shape.mermaid();
+            writeln!(out, "    n{idx}{open}{}{close}", quote(label)).unwrap();
+        }
+        for edge in &self.edges {
+            write!(out, "    n{} -->", edge.from).unwrap();
+            if let Some(label) = &edge.label {
+                write!(out, "|{}|", quote(label)).unwrap();
+            }
+            writeln!(out, " n{}", edge.to).unwrap();
+        }
+        out
+    }
+}
+
+impl From<&Graph> for GraphExport {
+    fn from(graph: &Graph) -> Self {
+        let mut export = Self::new();
+        for node in graph.nodes() {
+            export.node(graph.name(node), Shape::default());
+            for &next in graph.successors(node) {
+                export.edge(graph.name(node), graph.name(next));
+            }
+        }
+        export
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn export_works() {
+        let mut export = GraphExport::new();
+        export
+            .node("in", Shape::Box)
+            .node_with_label("end", Shape::Diamond, "the \"end\"")
+            .edge_with_label("in", "end", "x<3")
+            .edge("in", "out");
+        assert_eq!(
+            export.to_dot(),
+            indoc! {r#"
+                digraph {
+                    "in" [label="in", shape=box];
+                    "end" [label="the \"end\"", shape=diamond];
+                    "out" [label="out", shape=ellipse];
+                    "in" -> "end" [label="x<3"];
+                    "in" -> "out";
+                }
+            "#}
+        );
+        assert_eq!(
+            export.to_mermaid(),
+            indoc! {r#"
+                flowchart LR
+                    n0["in"]
+                    n1{"the #quot;end#quot;"}
+                    n2("out")
+                    n0 -->|"x<3"| n1
+                    n0 --> n2
+            "#}
+        );
+    }
+}
@@ -96,7 +96,7 @@ impl Turn {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Grid<T> {
     pub w: usize,
     pub h: usize,
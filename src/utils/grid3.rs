@@ -0,0 +1,288 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write;
+
+use itertools::{Itertools, iproduct};
+
+use crate::utils::point3::{Aabb, Point3};
+
+pub type Voxel = Point3<i64>;
+
+/// Dense 3D grid covering a bounding box.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid3<T> {
+    bounds: Aabb<i64>,
+    size: [usize; 3],
+    data: Vec<T>,
+}
+
+impl<T: Clone> Grid3<T> {
+    /// # Panics
+    ///
+    /// Will panic if the bounds are inverted.
+    pub fn new(bounds: Aabb<i64>, value: T) -> Self {
+        let size =
+            [0, 1, 2].map(|axis| usize::try_from(bounds.max[axis] - bounds.min[axis] + 1).unwrap());
+        Self {
+            bounds,
+            size,
+            data: vec![value; size.iter().product()],
+        }
+    }
+}
+
+impl<T> Grid3<T> {
+    pub fn bounds(&self) -> Aabb<i64> {
+        self.bounds
+    }
+
+    pub fn contains(&self, voxel: &Voxel) -> bool {
+        self.bounds.contains(voxel)
+    }
+
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    fn index(&self, voxel: &Voxel) -> Option<usize> {
+        if !self.contains(voxel) {
+            return None;
+        }
+        let [x, y, z] = [0, 1, 2].map(|axis| (voxel[axis] - self.bounds.min[axis]) as usize);
+        Some((z * self.size[1] + y) * self.size[0] + x)
+    }
+
+    pub fn get(&self, voxel: &Voxel) -> Option<&T> {
+        self.index(voxel).map(|idx| &self.data[idx])
+    }
+
+    pub fn get_mut(&mut self, voxel: &Voxel) -> Option<&mut T> {
+        self.index(voxel).map(|idx| &mut self.data[idx])
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if out of bounds.
+    pub fn set(&mut self, voxel: &Voxel, value: T) {
+        *self.get_mut(voxel).unwrap() = value;
+    }
+
+    /// All voxels of the grid, x varying fastest.
+    pub fn voxels(&self) -> impl Iterator<Item = Voxel> + use<T> {
+        let Aabb { min, max } = self.bounds;
+        iproduct!(min.z..=max.z, min.y..=max.y, min.x..=max.x).map(|(z, y, x)| Point3::new(x, y, z))
+    }
+
+    /// Face-adjacent voxels that are within the grid.
+    pub fn neighbors(&self, voxel: &Voxel) -> impl Iterator<Item = Voxel> + '_ {
+        voxel.neighbors6().into_iter().filter(|n| self.contains(n))
+    }
+
+    /// Voxels reachable from `start` through face-adjacent voxels whose value is `passable`.
+    pub fn flood_fill<F>(&self, start: &Voxel, passable: F) -> HashSet<Voxel>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut seen = HashSet::new();
+        if !self.get(start).is_some_and(&passable) {
+            return seen;
+        }
+        let mut queue = VecDeque::from([*start]);
+        seen.insert(*start);
+        while let Some(voxel) = queue.pop_front() {
+            for next in self.neighbors(&voxel) {
+                if self.get(&next).is_some_and(&passable) && seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        seen
+    }
+}
+
+impl<U: PartialEq> Grid3<Option<U>> {
+    /// Text projection of the grid along `depth_axis`, for debugging.
+    ///
+    /// Looking from the side (depth along x or y), rows are z levels from top to bottom; looking
+    /// from the top (depth along z), rows are y values. Each character shows the single value
+    /// found along the depth axis (via `label`), `.` if there is none, or `?` if there are several.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `depth_axis` is not 0, 1 or 2.
+    pub fn projection<F>(&self, depth_axis: usize, label: F) -> String
+    where
+        F: Fn(&U) -> char,
+    {
+        let (col_axis, row_axis) = match depth_axis {
+            0 => (1, 2),
+            1 => (0, 2),
+            2 => (0, 1),
+            _ => panic!("Invalid axis: {depth_axis}"),
+        };
+        let range = |axis: usize| self.bounds.min[axis]..=self.bounds.max[axis];
+        let rows = if row_axis == 2 {
+            range(row_axis).rev().collect_vec()
+        } else {
+            range(row_axis).collect_vec()
+        };
+
+        let mut out = String::new();
+        for row in rows {
+            for col in range(col_axis) {
+                let mut values: Vec<&U> = vec![];
+                for depth in range(depth_axis) {
+                    let mut coords = [0; 3];
+                    coords[depth_axis] = depth;
+                    coords[col_axis] = col;
+                    coords[row_axis] = row;
+                    if let Some(Some(value)) = self.get(&Point3::from(coords))
+                        && !values.contains(&value)
+                    {
+                        values.push(value);
+                    }
+                }
+                out.push(match values.as_slice() {
+                    [] => '.',
+                    [value] => label(value),
+                    _ => '?',
+                });
+            }
+            writeln!(out, " {row}").unwrap();
+        }
+        out
+    }
+}
+
+/// Sparse set of voxels, e.g. the cubes of a shape.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct VoxelSet {
+    voxels: HashSet<Voxel>,
+}
+
+impl FromIterator<Voxel> for VoxelSet {
+    fn from_iter<I: IntoIterator<Item = Voxel>>(iter: I) -> Self {
+        Self {
+            voxels: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl VoxelSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, voxel: Voxel) -> bool {
+        self.voxels.insert(voxel)
+    }
+
+    pub fn remove(&mut self, voxel: &Voxel) -> bool {
+        self.voxels.remove(voxel)
+    }
+
+    pub fn contains(&self, voxel: &Voxel) -> bool {
+        self.voxels.contains(voxel)
+    }
+
+    pub fn len(&self) -> usize {
+        self.voxels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Voxel> {
+        self.voxels.iter()
+    }
+
+    pub fn bounds(&self) -> Option<Aabb<i64>> {
+        Aabb::from_points(self.voxels.iter().copied())
+    }
+
+    /// Number of voxel faces not touching another voxel of the set.
+    pub fn surface_area(&self) -> usize {
+        self.faces_touching(|n| !self.contains(n))
+    }
+
+    /// Empty voxels connected to the outside, within the bounds grown by one.
+    #[must_use]
+    pub fn exterior(&self) -> VoxelSet {
+        let Some(bounds) = self.bounds() else {
+            return VoxelSet::new();
+        };
+        let bounds = bounds.expand(1);
+        let mut grid = Grid3::new(bounds, false);
+        self.voxels.iter().for_each(|voxel| grid.set(voxel, true));
+        let outside = grid.flood_fill(&bounds.min, |&filled| !filled);
+        VoxelSet { voxels: outside }
+    }
+
+    /// Number of voxel faces reachable from the outside, ignoring internal cavities.
+    pub fn exterior_surface_area(&self) -> usize {
+        let exterior = self.exterior();
+        self.faces_touching(|n| exterior.contains(n))
+    }
+
+    fn faces_touching<F: Fn(&Voxel) -> bool>(&self, f: F) -> usize {
+        self.voxels
+            .iter()
+            .flat_map(Voxel::neighbors6)
+            .filter(|n| f(n))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn voxel_set_works() {
+        // 3x3x3 cube with a hole in the middle
+        let mut cube: VoxelSet = iproduct!(0..3, 0..3, 0..3)
+            .map(|(x, y, z)| Point3::new(x, y, z))
+            .collect();
+        cube.remove(&Point3::new(1, 1, 1));
+        assert_eq!(cube.len(), 26);
+        assert_eq!(cube.surface_area(), 6 * 9 + 6);
+        assert_eq!(cube.exterior_surface_area(), 6 * 9);
+        assert_eq!(cube.exterior().len(), 5 * 5 * 5 - 27);
+    }
+
+    #[test]
+    fn grid3_works() {
+        let bounds = Aabb {
+            min: Point3::new(0, 0, 1),
+            max: Point3::new(2, 1, 3),
+        };
+        let mut grid = Grid3::new(bounds, None);
+        grid.set(&Point3::new(0, 0, 1), Some('A'));
+        grid.set(&Point3::new(1, 0, 1), Some('A'));
+        grid.set(&Point3::new(1, 1, 1), Some('B'));
+        grid.set(&Point3::new(2, 1, 3), Some('C'));
+        assert_eq!(grid.voxels().count(), 18);
+        assert_eq!(grid.get(&Point3::new(3, 0, 1)), None);
+        assert_eq!(grid.neighbors(&Point3::new(0, 0, 1)).count(), 3);
+        assert_eq!(
+            grid.flood_fill(&Point3::new(0, 0, 1), Option::is_some)
+                .len(),
+            3
+        );
+
+        assert_eq!(
+            grid.projection(1, |&c| c),
+            indoc! {"
+                ..C 3
+                ... 2
+                A?. 1
+            "}
+        );
+        assert_eq!(
+            grid.projection(2, |&c| c),
+            indoc! {"
+                AA. 0
+                .BC 1
+            "}
+        );
+    }
+}
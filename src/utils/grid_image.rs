@@ -0,0 +1,193 @@
+use std::io::Write;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow, ensure};
+use indexmap::IndexSet;
+use itertools::Itertools;
+
+use crate::utils::grid::{Coord, Grid};
+
+pub type Rgb = [u8; 3];
+
+/// Renders grids to images (PNG snapshots, animated GIFs, SVG), one `scale`×`scale` square per cell.
+pub struct GridImage<F> {
+    pub scale: usize,
+    pub color: F,
+}
+
+impl<F> GridImage<F> {
+    pub fn new(color: F) -> Self {
+        Self { scale: 4, color }
+    }
+
+    #[must_use]
+    pub fn with_scale(self, scale: usize) -> Self {
+        Self { scale, ..self }
+    }
+
+    fn cell_colors<T>(&self, grid: &Grid<T>) -> Vec<Rgb>
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        grid.coords().map(|c| (self.color)(grid.get(&c))).collect()
+    }
+
+    /// Upscales cell-level values to pixel-level values (row-major).
+    fn pixels<V: Copy>(&self, w: usize, h: usize, cells: &[V]) -> Vec<V> {
+        (0..h * self.scale)
+            .flat_map(|py| (0..w * self.scale).map(move |px| (px, py)))
+            .map(|(px, py)| cells[px / self.scale + py / self.scale * w])
+            .collect()
+    }
+
+    pub fn write_png<T, W: Write>(&self, grid: &Grid<T>, w: W) -> Result<()>
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        let cells = self.cell_colors(grid);
+        let data = self
+            .pixels(grid.w, grid.h, &cells)
+            .into_iter()
+            .flatten()
+            .collect_vec();
+        let mut encoder = png::Encoder::new(
+            w,
+            u32::try_from(grid.w * self.scale)?,
+            u32::try_from(grid.h * self.scale)?,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Writes an endlessly looping animated GIF, with `delay` between frames (rounded to 10 ms).
+    ///
+    /// All frames must have the same size, and use at most 256 distinct colors overall.
+    pub fn write_gif<T, W: Write>(&self, frames: &[Grid<T>], delay: Duration, w: W) -> Result<()>
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        ensure!(!frames.is_empty(), "No frames to write");
+        let (grid_w, grid_h) = (frames[0].w, frames[0].h);
+        ensure!(
+            frames.iter().all(|f| f.w == grid_w && f.h == grid_h),
+            "All frames must have the same size"
+        );
+
+        let mut palette: IndexSet<Rgb> = IndexSet::new();
+        let indexed_frames = frames
+            .iter()
+            .map(|grid| {
+                self.cell_colors(grid)
+                    .into_iter()
+                    .map(|rgb| u8::try_from(palette.insert_full(rgb).0))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow!("More than 256 colors: {}", e))?;
+        let palette = palette.into_iter().flatten().collect_vec();
+
+        let width = u16::try_from(grid_w * self.scale)?;
+        let height = u16::try_from(grid_h * self.scale)?;
+        let delay = u16::try_from(delay.as_millis() / 10)?;
+        let mut encoder = gif::Encoder::new(w, width, height, &palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for cells in indexed_frames {
+            let mut frame = gif::Frame::from_indexed_pixels(
+                width,
+                height,
+                self.pixels(grid_w, grid_h, &cells),
+                None,
+            );
+            frame.delay = delay;
+            encoder.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+
+    /// Writes an SVG document, merging horizontal runs of same-colored cells into a single rectangle.
+    pub fn write_svg<T, W: Write>(&self, grid: &Grid<T>, mut out: W) -> Result<()>
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        let scale = self.scale;
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" shape-rendering="crispEdges">"#,
+            grid.w * scale,
+            grid.h * scale
+        )?;
+        for y in 0..grid.h {
+            let row = (0..grid.w)
+                .map(|x| (self.color)(grid.get(&Coord(x, y))))
+                .collect_vec();
+            let mut x = 0;
+            for (rgb, run) in &row.into_iter().chunk_by(|rgb| *rgb) {
+                let len = run.count();
+                let [r, g, b] = rgb;
+                writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#{r:02x}{g:02x}{b:02x}\"/>",
+                    x * scale,
+                    y * scale,
+                    len * scale,
+                    scale
+                )?;
+                x += len;
+            }
+        }
+        writeln!(out, "</svg>")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> GridImage<impl Fn(&char) -> Rgb> {
+        GridImage::new(|c: &char| match c {
+            '#' => [0, 0, 0],
+            'O' => [255, 0, 0],
+            _ => [255, 255, 255],
+        })
+        .with_scale(2)
+    }
+
+    #[test]
+    fn write_png_works() {
+        let grid: Grid<char> = Grid::from_lines(&["#.O", "..#"]).unwrap();
+        let mut buf = Vec::new();
+        image().write_png(&grid, &mut buf).unwrap();
+
+        let decoder = png::Decoder::new(std::io::Cursor::new(buf));
+        let reader = decoder.read_info().unwrap();
+        assert_eq!(
+            (reader.info().width, reader.info().height),
+            (6, 4),
+            "image should be scaled"
+        );
+    }
+
+    #[test]
+    fn write_gif_and_svg_work() {
+        let frames: Vec<Grid<char>> = vec![
+            Grid::from_lines(&["O.", "#."]).unwrap(),
+            Grid::from_lines(&[".O", "#."]).unwrap(),
+        ];
+        let mut buf = Vec::new();
+        image()
+            .write_gif(&frames, Duration::from_millis(100), &mut buf)
+            .unwrap();
+        assert!(buf.starts_with(b"GIF89a"));
+
+        let mut buf = Vec::new();
+        image().write_svg(&frames[0], &mut buf).unwrap();
+        let svg = String::from_utf8(buf).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.contains(r##"<rect x="2" y="2" width="2" height="2" fill="#ffffff"/>"##));
+    }
+}
@@ -0,0 +1,189 @@
+use std::iter::{Product, Sum};
+use std::ops::{Range, Sub};
+
+/// Axis-aligned box in `N` dimensions, spanning `min[axis]..max[axis]` (half-open) on each axis.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct HyperRect<T, const N: usize> {
+    pub min: [T; N],
+    pub max: [T; N],
+}
+
+impl<T, const N: usize> HyperRect<T, N>
+where
+    T: Copy + Ord,
+{
+    pub fn new(min: [T; N], max: [T; N]) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_ranges(ranges: [Range<T>; N]) -> Self {
+        Self {
+            min: ranges.clone().map(|r| r.start),
+            max: ranges.map(|r| r.end),
+        }
+    }
+
+    pub fn ranges(&self) -> [Range<T>; N] {
+        std::array::from_fn(|axis| self.min[axis]..self.max[axis])
+    }
+
+    /// Same box, restricted to `range` on the given axis.
+    #[must_use]
+    pub fn with_axis(&self, axis: usize, range: Range<T>) -> Self {
+        let mut result = *self;
+        result.min[axis] = range.start;
+        result.max[axis] = range.end;
+        result
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|axis| self.min[axis] >= self.max[axis])
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        (0..N).all(|axis| self.min[axis] <= point[axis] && point[axis] < self.max[axis])
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let result = Self {
+            min: std::array::from_fn(|axis| self.min[axis].max(other.min[axis])),
+            max: std::array::from_fn(|axis| self.max[axis].min(other.max[axis])),
+        };
+        (!result.is_empty()).then_some(result)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Splits `self \ other` into at most `2 * N` disjoint boxes.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        if self.is_empty() {
+            return vec![];
+        }
+        let Some(inter) = self.intersection(other) else {
+            return vec![*self];
+        };
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for axis in 0..N {
+            // slabs of `rest` on both sides of the intersection along this axis
+            let below = rest.with_axis(axis, rest.min[axis]..inter.min[axis]);
+            let above = rest.with_axis(axis, inter.max[axis]..rest.max[axis]);
+            pieces.extend([below, above].into_iter().filter(|b| !b.is_empty()));
+            rest = rest.with_axis(axis, inter.min[axis]..inter.max[axis]);
+        }
+        pieces
+    }
+
+    pub fn volume(&self) -> T
+    where
+        T: Sub<Output = T> + Product,
+    {
+        (0..N)
+            .map(|axis| self.max[axis].max(self.min[axis]) - self.min[axis])
+            .product()
+    }
+}
+
+/// Union of boxes, stored as pairwise disjoint boxes.
+#[derive(Debug, Clone)]
+pub struct HyperRectSet<T, const N: usize> {
+    rects: Vec<HyperRect<T, N>>,
+}
+
+impl<T, const N: usize> Default for HyperRectSet<T, N> {
+    fn default() -> Self {
+        Self { rects: Vec::new() }
+    }
+}
+
+impl<T, const N: usize> FromIterator<HyperRect<T, N>> for HyperRectSet<T, N>
+where
+    T: Copy + Ord,
+{
+    fn from_iter<I: IntoIterator<Item = HyperRect<T, N>>>(iter: I) -> Self {
+        let mut set = Self::default();
+        for rect in iter {
+            set.insert(&rect);
+        }
+        set
+    }
+}
+
+impl<T, const N: usize> HyperRectSet<T, N>
+where
+    T: Copy + Ord,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rects(&self) -> &[HyperRect<T, N>] {
+        &self.rects
+    }
+
+    pub fn insert(&mut self, rect: &HyperRect<T, N>) {
+        let mut pieces = vec![*rect];
+        for existing in &self.rects {
+            pieces = pieces.iter().flat_map(|p| p.subtract(existing)).collect();
+        }
+        self.rects.extend(pieces);
+    }
+
+    pub fn remove(&mut self, rect: &HyperRect<T, N>) {
+        self.rects = self.rects.iter().flat_map(|r| r.subtract(rect)).collect();
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.rects.iter().any(|r| r.contains(point))
+    }
+
+    pub fn volume(&self) -> T
+    where
+        T: Sub<Output = T> + Product + Sum,
+    {
+        self.rects.iter().map(HyperRect::volume).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn subtract_works() {
+        let a = HyperRect::new([0, 0, 0], [4, 4, 4]);
+        let b = HyperRect::new([1, 1, 1], [2, 2, 5]);
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.len(), 5);
+        assert_eq!(pieces.iter().map(HyperRect::volume).sum::<i32>(), 64 - 3);
+        assert!(
+            pieces
+                .iter()
+                .tuple_combinations()
+                .all(|(p, q)| !p.intersects(q))
+        );
+        assert_eq!(a.subtract(&HyperRect::new([5, 5, 5], [6, 6, 6])), vec![a]);
+        assert!(a.subtract(&a).is_empty());
+    }
+
+    #[test]
+    fn hyper_rect_set_works() {
+        let set: HyperRectSet<u64, 2> = [
+            HyperRect::from_ranges([0..10, 0..10]),
+            HyperRect::from_ranges([5..15, 5..15]),
+            HyperRect::from_ranges([2..3, 2..3]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.volume(), 175);
+        assert!(set.contains(&[14, 14]) && !set.contains(&[14, 4]));
+
+        let mut set = set;
+        set.remove(&HyperRect::from_ranges([0..15, 0..5]));
+        assert_eq!(set.volume(), 125);
+    }
+}
@@ -0,0 +1,288 @@
+use std::iter::Sum;
+use std::ops::{Add, Range, Sub};
+
+use itertools::Itertools;
+
+/// Set of values, stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::from_iter([range])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<Range<T>> = Vec::new();
+        for range in iter
+            .into_iter()
+            .filter(|r| r.start < r.end)
+            .sorted_by_key(|r| r.start)
+        {
+            match ranges.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => ranges.push(range),
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+        // the ranges overlapping or adjacent to the new one are merged into it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let (mut start, mut end) = (range.start, range.end);
+        if first < last {
+            start = start.min(self.ranges[first].start);
+            end = end.max(self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, [start..end]);
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.ranges.iter().chain(&other.ranges).cloned().collect()
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        for range in &self.ranges {
+            let mut start = range.start;
+            let first = other.ranges.partition_point(|o| o.end <= start);
+            for o in other.ranges[first..]
+                .iter()
+                .take_while(|o| o.start < range.end)
+            {
+                if start < o.start {
+                    ranges.push(start..o.start);
+                }
+                start = start.max(o.end);
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Splits into the values lower than `at`, and the values greater than or equal to `at`.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let lower = self.ranges.iter().filter_map(|r| {
+            let end = r.end.min(at);
+            (r.start < end).then_some(r.start..end)
+        });
+        let upper = self.ranges.iter().filter_map(|r| {
+            let start = r.start.max(at);
+            (start < r.end).then_some(start..r.end)
+        });
+        (lower.collect(), upper.collect())
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end <= *value);
+        self.ranges.get(idx).is_some_and(|r| r.start <= *value)
+    }
+
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.start >= range.end {
+            return true;
+        }
+        let idx = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(idx)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    pub fn overlaps_range(&self, range: &Range<T>) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(idx)
+            .is_some_and(|r| r.start < range.end && range.start < range.end)
+    }
+
+    /// Number of values in the set.
+    pub fn total_len(&self) -> T
+    where
+        T: Sub<Output = T> + Sum,
+    {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+}
+
+/// Piecewise-linear mapping: values within a source range are translated to the corresponding
+/// destination range, other values are mapped to themselves.
+#[derive(Debug, Clone)]
+pub struct RangeMap<T> {
+    entries: Vec<(Range<T>, T)>, // (source range, destination start), sorted by source start
+}
+
+impl<T> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> RangeMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `source` to `destination_start..destination_start + source.len()`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `source` overlaps a previously inserted source range.
+    pub fn insert(&mut self, source: Range<T>, destination_start: T) {
+        let idx = self
+            .entries
+            .partition_point(|(r, _)| r.start < source.start);
+        assert!(
+            self.entries
+                .get(idx)
+                .is_none_or(|(next, _)| source.end <= next.start),
+            "Overlapping source ranges"
+        );
+        assert!(
+            idx == 0 || self.entries[idx - 1].0.end <= source.start,
+            "Overlapping source ranges"
+        );
+        self.entries.insert(idx, (source, destination_start));
+    }
+
+    pub fn map(&self, value: T) -> T {
+        let idx = self.entries.partition_point(|(r, _)| r.end <= value);
+        match self.entries.get(idx) {
+            Some((r, dest)) if r.start <= value => *dest + (value - r.start),
+            _ => value,
+        }
+    }
+
+    /// Image of a set of values.
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let sources: IntervalSet<T> = self.entries.iter().map(|(r, _)| r.clone()).collect();
+        let unmapped = set.difference(&sources);
+        let mapped = self.entries.iter().flat_map(|(source, dest)| {
+            set.intersection(&IntervalSet::from(source.clone()))
+                .ranges
+                .into_iter()
+                .map(|r| *dest + (r.start - source.start)..*dest + (r.end - source.start))
+        });
+        unmapped.ranges.into_iter().chain(mapped).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn interval_set_works() {
+        let a: IntervalSet<u32> = [5..10, 0..3, 2..4, 10..12].into_iter().collect();
+        assert_eq!(a.ranges(), &[0..4, 5..12]);
+        let b = IntervalSet::from(3..6);
+        assert_eq!(a.union(&b).ranges(), &[0..12]);
+        assert_eq!(a.intersection(&b).ranges(), &[3..4, 5..6]);
+        assert_eq!(a.difference(&b).ranges(), &[0..3, 6..12]);
+        assert_eq!(b.difference(&a).ranges(), &[4..5]);
+        let (lower, upper) = a.split_at(6);
+        assert_eq!(
+            (lower.ranges(), upper.ranges()),
+            (&[0..4, 5..6][..], &[6..12][..])
+        );
+        assert!(a.contains(&0) && a.contains(&11) && !a.contains(&4) && !a.contains(&12));
+        assert!(a.contains_range(&(5..12)) && !a.contains_range(&(3..6)));
+        assert!(a.overlaps_range(&(3..6)) && !a.overlaps_range(&(4..5)));
+        assert_eq!(a.total_len(), 11);
+
+        let mut c = a.clone();
+        c.insert(14..16);
+        c.insert(13..14);
+        c.insert(7..7);
+        assert_eq!(c.ranges(), &[0..4, 5..12, 13..16]);
+        c.insert(4..5);
+        c.insert(11..13);
+        assert_eq!(c.ranges(), &[0..16]);
+    }
+
+    proptest! {
+        #[test]
+        fn insert_matches_collect(ranges in prop::collection::vec((0..100u32, 0..20u32), 0..20)) {
+            let ranges = ranges.into_iter().map(|(start, len)| start..start + len).collect_vec();
+            let mut set = IntervalSet::new();
+            for range in &ranges {
+                set.insert(range.clone());
+            }
+            prop_assert_eq!(set, ranges.into_iter().collect::<IntervalSet<_>>());
+        }
+    }
+
+    #[test]
+    fn range_map_works() {
+        let mut map = RangeMap::new();
+        map.insert(98..100, 50);
+        map.insert(50..98, 52);
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(99), 51);
+        assert_eq!(map.map(13), 13);
+        let set = map.map_set(&IntervalSet::from(40..60));
+        assert_eq!(set.ranges(), &[40..50, 52..62]);
+    }
+}
@@ -0,0 +1,211 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::{Add, Mul, Sub};
+
+use crate::utils::point3::Point3;
+
+/// Static k-d tree over 3D points, answering nearest-neighbour queries.
+///
+/// Points are referred to by their index in the slice the tree was built from. Ties in distance
+/// are broken by index, so that results are deterministic.
+#[derive(Debug, Clone)]
+pub struct KdTree<T> {
+    points: Vec<Point3<T>>,
+    // implicit balanced tree: the node of range [lo, hi) is at its middle, split on depth % 3
+    items: Vec<(Point3<T>, usize)>,
+}
+
+impl<T> KdTree<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    pub fn new(points: &[Point3<T>]) -> Self {
+        let mut items: Vec<_> = points.iter().copied().zip(0..).collect();
+        Self::build(&mut items, 0);
+        Self {
+            points: points.to_vec(),
+            items,
+        }
+    }
+
+    fn build(items: &mut [(Point3<T>, usize)], depth: usize) {
+        if items.len() <= 1 {
+            return;
+        }
+        let mid = items.len() / 2;
+        let axis = depth % 3;
+        items.select_nth_unstable_by_key(mid, |(p, idx)| (p[axis], *idx));
+        let (left, right) = items.split_at_mut(mid);
+        Self::build(left, depth + 1);
+        Self::build(&mut right[1..], depth + 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The `k` points closest to `query`, as `(index, squared distance)`, closest first.
+    pub fn nearest(&self, query: &Point3<T>, k: usize) -> Vec<(usize, T)> {
+        if k == 0 {
+            return vec![];
+        }
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        self.search(query, k, 0, self.items.len(), 0, &mut best);
+        let mut result: Vec<_> = best.into_iter().map(|(dist2, idx)| (idx, dist2)).collect();
+        result.sort_unstable_by_key(|&(idx, dist2)| (dist2, idx));
+        result
+    }
+
+    fn search(
+        &self,
+        query: &Point3<T>,
+        k: usize,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        best: &mut BinaryHeap<(T, usize)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let (point, idx) = self.items[mid];
+        best.push((point.dist2(query), idx));
+        if best.len() > k {
+            best.pop();
+        }
+
+        let axis = depth % 3;
+        let (near, far) = if query[axis] < point[axis] {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.search(query, k, near.0, near.1, depth + 1, best);
+        let plane = query[axis].max(point[axis]) - query[axis].min(point[axis]);
+        // the far side may still hold points at the same distance but with a smaller index
+        if best.len() < k
+            || best
+                .peek()
+                .is_some_and(|&(worst, _)| plane * plane <= worst)
+        {
+            self.search(query, k, far.0, far.1, depth + 1, best);
+        }
+    }
+
+    /// All pairs of distinct points `(i, j, squared distance)` with `i < j`, closest first.
+    ///
+    /// Pairs are generated lazily, so taking the first few does not cost the full pair matrix.
+    pub fn closest_pairs(&self) -> ClosestPairs<'_, T> {
+        let mut pairs = ClosestPairs {
+            tree: self,
+            neighbors: vec![vec![]; self.len()],
+            cursors: vec![0; self.len()],
+            heap: BinaryHeap::new(),
+        };
+        for i in 0..self.len() {
+            pairs.push_next(i);
+        }
+        pairs
+    }
+}
+
+/// Iterator over the pairs of points of a [`KdTree`], in increasing distance.
+///
+/// Each point tracks its next-nearest neighbour, fetched from the tree in growing batches.
+#[derive(Debug, Clone)]
+pub struct ClosestPairs<'a, T> {
+    tree: &'a KdTree<T>,
+    neighbors: Vec<Vec<(usize, T)>>,
+    cursors: Vec<usize>,
+    heap: BinaryHeap<Reverse<(T, usize, usize)>>,
+}
+
+impl<T> ClosestPairs<'_, T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    const FIRST_BATCH: usize = 4;
+
+    /// Queues the next neighbour of point `i`, if any.
+    fn push_next(&mut self, i: usize) {
+        let cursor = self.cursors[i];
+        let fetched = self.neighbors[i].len();
+        if cursor == fetched && fetched + 1 < self.tree.len() {
+            let k = (2 * fetched).max(Self::FIRST_BATCH) + 1; // +1 for the point itself
+            self.neighbors[i] = self
+                .tree
+                .nearest(&self.tree.points[i], k)
+                .into_iter()
+                .filter(|&(j, _)| j != i)
+                .collect();
+        }
+        if let Some(&(j, dist2)) = self.neighbors[i].get(cursor) {
+            self.cursors[i] += 1;
+            self.heap.push(Reverse((dist2, i, j)));
+        }
+    }
+}
+
+impl<T> Iterator for ClosestPairs<'_, T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Item = (usize, usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Reverse((dist2, i, j)) = self.heap.pop()?;
+            self.push_next(i);
+            // every pair is found from both ends: only keep one
+            if i < j {
+                return Some((i, j, dist2));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn points() -> Vec<Point3<i64>> {
+        (0..60_i64)
+            .map(|i| Point3::new((i * 37) % 23, (i * 11) % 17 - 8, (i * i) % 13))
+            .collect()
+    }
+
+    #[test]
+    fn nearest_works() {
+        let points = points();
+        let tree = KdTree::new(&points);
+        let query = Point3::new(5, 0, 5);
+        let mut expected = points
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| (idx, p.dist2(&query)))
+            .collect_vec();
+        expected.sort_by_key(|&(idx, dist2)| (dist2, idx));
+        for k in [1, 7, 60, 100] {
+            assert_eq!(tree.nearest(&query, k), expected[..k.min(60)]);
+        }
+    }
+
+    #[test]
+    fn closest_pairs_works() {
+        let points = points();
+        let tree = KdTree::new(&points);
+        let mut expected = (0..points.len())
+            .tuple_combinations()
+            .map(|(i, j)| (i, j, points[i].dist2(&points[j])))
+            .collect_vec();
+        expected.sort_by_key(|&(i, j, dist2)| (dist2, i, j));
+        assert_eq!(tree.closest_pairs().collect_vec(), expected);
+    }
+}
@@ -0,0 +1,304 @@
+use anyhow::{Result, bail, ensure};
+use itertools::Itertools;
+
+use crate::utils::math::extended_gcd;
+use crate::utils::matrix::Matrix;
+use crate::utils::rational::Rational;
+
+/// Integer program in equality form: minimize `c·x` subject to `A x = b`, `x ≥ 0`, `x` integer.
+#[derive(Debug, Clone)]
+pub struct IntegerProgram {
+    a: Vec<Vec<i64>>,
+    b: Vec<i64>,
+    c: Vec<i64>,
+}
+
+impl IntegerProgram {
+    /// `a` has one row per constraint and one column per variable.
+    pub fn new(a: Vec<Vec<i64>>, b: Vec<i64>, c: Vec<i64>) -> Result<Self> {
+        ensure!(
+            a.len() == b.len(),
+            "{} rows but {} right-hand sides",
+            a.len(),
+            b.len()
+        );
+        ensure!(
+            a.iter().all(|row| row.len() == c.len()),
+            "Rows must have one coefficient per variable ({})",
+            c.len()
+        );
+        Ok(Self { a, b, c })
+    }
+
+    pub fn variables(&self) -> usize {
+        self.c.len()
+    }
+
+    pub fn objective(&self, x: &[i64]) -> i64 {
+        self.c.iter().zip(x).map(|(c, x)| c * x).sum()
+    }
+
+    pub fn is_feasible(&self, x: &[i64]) -> bool {
+        x.len() == self.variables()
+            && x.iter().all(|&x| x >= 0)
+            && self
+                .a
+                .iter()
+                .zip(&self.b)
+                .all(|(row, &b)| row.iter().zip(x).map(|(a, x)| a * x).sum::<i64>() == b)
+    }
+
+    /// Optimal solution, or `None` if the program is infeasible.
+    ///
+    /// Uses `good_lp` when the feature is enabled, and the exact solver otherwise.
+    pub fn solve(&self) -> Result<Option<Vec<i64>>> {
+        #[cfg(feature = "good_lp")]
+        return self.solve_lp();
+        #[cfg(not(feature = "good_lp"))]
+        return self.solve_exact();
+    }
+
+    /// Solves with `good_lp`, checking that the returned solution is integral and feasible.
+    #[cfg(feature = "good_lp")]
+    pub fn solve_lp(&self) -> Result<Option<Vec<i64>>> {
+        use good_lp::{
+            Expression, ProblemVariables, ResolutionError, Solution, SolverModel, highs, variable,
+        };
+
+        let mut vars = ProblemVariables::new();
+        let xs = (0..self.variables())
+            .map(|_| vars.add(variable().integer().min(0)))
+            .collect_vec();
+        let expression = |coefficients: &[i64]| -> Result<Expression> {
+            let mut expr = Expression::from(0);
+            for (&coef, &x) in coefficients.iter().zip(&xs) {
+                if coef != 0 {
+                    expr += i32::try_from(coef)? * x;
+                }
+            }
+            Ok(expr)
+        };
+
+        let mut problem = vars.minimise(expression(&self.c)?).using(highs);
+        for (row, &b) in self.a.iter().zip(&self.b) {
+            problem.add_constraint(expression(row)?.eq(i32::try_from(b)?));
+        }
+        let solution = match problem.solve() {
+            Ok(solution) => solution,
+            Err(ResolutionError::Infeasible) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let x = xs
+            .iter()
+            .map(|&x| {
+                let value = solution.value(x);
+                let rounded = value.round();
+                ensure!(
+                    (value - rounded).abs() < 1e-6,
+                    "Non-integral value: {value}"
+                );
+                #[allow(clippy::cast_possible_truncation)]
+                Ok(rounded as i64)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        ensure!(
+            self.is_feasible(&x),
+            "Solver returned an infeasible solution: {x:?}"
+        );
+        Ok(Some(x))
+    }
+
+    /// Exact solver: Gaussian elimination over rationals, then exhaustive search over the free
+    /// variables.
+    ///
+    /// Each free variable must be bounded by a constraint with only non-negative coefficients
+    /// (e.g. `x + y = 5` bounds both `x` and `y`); this keeps the search finite.
+    pub fn solve_exact(&self) -> Result<Option<Vec<i64>>> {
+        let n = self.variables();
+        let upper = self.upper_bounds();
+
+        let Some(echelon) = Echelon::new(&self.a, &self.b) else {
+            return Ok(None);
+        };
+        let free = (0..n)
+            .filter(|var| !echelon.pivots.iter().any(|(col, _)| col == var))
+            .collect_vec();
+        for &var in &free {
+            if upper[var].is_none() {
+                bail!("Free variable x{var} is unbounded, the search would not terminate");
+            }
+        }
+
+        let mut search = Search {
+            program: self,
+            echelon: &echelon,
+            free: &free,
+            upper: &upper,
+            x: vec![0; n],
+            best: None,
+        };
+        search.run(0);
+        Ok(search.best.map(|(_, x)| x))
+    }
+
+    /// Upper bound of each variable implied by the non-negative constraints, if any.
+    fn upper_bounds(&self) -> Vec<Option<i64>> {
+        let mut upper = vec![None; self.variables()];
+        for (row, &b) in self.a.iter().zip(&self.b) {
+            if row.iter().any(|&a| a < 0) {
+                continue;
+            }
+            for (var, &a) in row.iter().enumerate() {
+                if a > 0 {
+                    let bound = b.div_euclid(a);
+                    upper[var] = Some(upper[var].map_or(bound, |u: i64| u.min(bound)));
+                }
+            }
+        }
+        upper
+    }
+}
+
+/// Reduced row echelon form of `[A | b]`, with each row scaled to integer coefficients.
+struct Echelon {
+    /// `(pivot column, row)`, where row holds the coefficients followed by the right-hand side.
+    pivots: Vec<(usize, Vec<i128>)>,
+}
+
+impl Echelon {
+    /// `None` if the system has no (rational) solution.
+    fn new(a: &[Vec<i64>], b: &[i64]) -> Option<Self> {
+        if a.is_empty() {
+            return Some(Self { pivots: vec![] });
+        }
+        let rows = a
+            .iter()
+            .zip(b)
+            .map(|(row, &b)| row.iter().chain([&b]).copied().collect_vec())
+            .collect_vec();
+        let (rref, pivot_cols) = Matrix::from_rows(&rows).rref();
+        let n = rref.cols() - 1;
+        if pivot_cols.last() == Some(&n) {
+            return None; // 0 = 1
+        }
+        let pivots = pivot_cols
+            .into_iter()
+            .enumerate()
+            .map(|(r, col)| {
+                let row = rref.row(r);
+                let scale = row
+                    .iter()
+                    .fold(1, |l, v| l / extended_gcd(l, v.denom()).0 * v.denom());
+                let row = row
+                    .iter()
+                    .map(|&v| (v * Rational::from(scale)).numer())
+                    .collect();
+                (col, row)
+            })
+            .collect();
+        Some(Self { pivots })
+    }
+}
+
+struct Search<'a> {
+    program: &'a IntegerProgram,
+    echelon: &'a Echelon,
+    free: &'a [usize],
+    upper: &'a [Option<i64>],
+    x: Vec<i64>,
+    best: Option<(i64, Vec<i64>)>,
+}
+
+impl Search<'_> {
+    fn run(&mut self, depth: usize) {
+        if let Some(&var) = self.free.get(depth) {
+            for value in 0..=self.upper[var].unwrap_or_default() {
+                self.x[var] = value;
+                self.run(depth + 1);
+            }
+            return;
+        }
+
+        // each pivot variable only depends on the free variables
+        for (col, row) in self.echelon.pivots.iter().rev() {
+            let rhs = row[row.len() - 1]
+                - (0..row.len() - 1)
+                    .filter(|&c| c != *col)
+                    .map(|c| row[c] * i128::from(self.x[c]))
+                    .sum::<i128>();
+            if rhs % row[*col] != 0 {
+                return;
+            }
+            let Ok(value) = i64::try_from(rhs / row[*col]) else {
+                return;
+            };
+            if value < 0 || self.upper[*col].is_some_and(|u| value > u) {
+                return;
+            }
+            self.x[*col] = value;
+        }
+
+        let objective = self.program.objective(&self.x);
+        if self.best.as_ref().is_none_or(|(best, _)| objective < *best) {
+            self.best = Some((objective, self.x.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> IntegerProgram {
+        // buttons (3) (1,3) (2) (2,3) (0,2) (0,1), joltages {3,5,4,7}
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let a = (0..4)
+            .map(|counter| {
+                buttons
+                    .iter()
+                    .map(|b| i64::from(b.contains(&counter)))
+                    .collect()
+            })
+            .collect();
+        IntegerProgram::new(a, vec![3, 5, 4, 7], vec![1; 6]).unwrap()
+    }
+
+    #[test]
+    fn solve_exact_works() {
+        let program = example();
+        let x = program.solve_exact().unwrap().unwrap();
+        assert!(program.is_feasible(&x));
+        assert_eq!(program.objective(&x), 10);
+
+        // x + y = 3, x - y = 2 has no integer solution
+        let program = IntegerProgram::new(vec![vec![1, 1], vec![1, -1]], vec![3, 2], vec![1, 1]);
+        assert_eq!(program.unwrap().solve_exact().unwrap(), None);
+        // x + y = 3, x + y = 4 is inconsistent
+        let program = IntegerProgram::new(vec![vec![1, 1], vec![1, 1]], vec![3, 4], vec![1, 1]);
+        assert_eq!(program.unwrap().solve_exact().unwrap(), None);
+        // x - y = 0 leaves y unbounded
+        let program = IntegerProgram::new(vec![vec![1, -1]], vec![0], vec![1, 1]);
+        assert!(program.unwrap().solve_exact().is_err());
+    }
+
+    #[test]
+    fn solve_exact_finds_known_optimum() {
+        // x + y + z = 4, x + 2y = 3: (3, 0, 1) costs 10, (1, 1, 2) costs 6
+        let program = IntegerProgram::new(
+            vec![vec![1, 1, 1], vec![1, 2, 0]],
+            vec![4, 3],
+            vec![3, 1, 1],
+        );
+        let x = program.unwrap().solve_exact().unwrap();
+        assert_eq!(x, Some(vec![1, 1, 2]));
+    }
+
+    #[cfg(feature = "good_lp")]
+    #[test]
+    fn solve_lp_works() {
+        let program = example();
+        let x = program.solve_lp().unwrap().unwrap();
+        assert_eq!(program.objective(&x), 10);
+    }
+}
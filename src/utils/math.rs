@@ -0,0 +1,333 @@
+pub fn gcd(a: u64, b: u64) -> u64 {
+    let (mut a, mut b) = (a, b);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or `None` if it overflows.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        Some(0)
+    } else {
+        (a / gcd(a, b)).checked_mul(b)
+    }
+}
+
+pub fn gcd_all<I: IntoIterator<Item = u64>>(values: I) -> u64 {
+    values.into_iter().fold(0, gcd)
+}
+
+/// Least common multiple of all the values, or `None` if it overflows.
+pub fn lcm_all<I: IntoIterator<Item = u64>>(values: I) -> Option<u64> {
+    values.into_iter().try_fold(1, lcm)
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g = gcd(a, b)`.
+#[allow(clippy::many_single_char_names)]
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Inverse of `a` modulo `m`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+#[allow(clippy::cast_possible_truncation)] // result is < m
+pub fn mod_mul(a: u64, b: u64, m: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+/// `base^exp mod m`, or `None` for a zero modulus.
+pub fn mod_pow(base: u64, exp: u64, m: u64) -> Option<u64> {
+    (m != 0).then(|| pow_mod_nonzero(base, exp, m))
+}
+
+fn pow_mod_nonzero(base: u64, exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    let mut base = base % m;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Chinese Remainder Theorem: solves the system `x ≡ r (mod m)` for each `(r, m)` congruence.
+///
+/// Moduli do not need to be pairwise coprime. Returns `(x, lcm)` with `0 <= x < lcm` (the solutions
+/// being `x + k * lcm`), or `None` if the system is inconsistent, a modulus is not positive, or the
+/// computation overflows.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences.iter().try_fold((0, 1), |(r1, m1), &(r2, m2)| {
+        if m2 <= 0 {
+            return None;
+        }
+        let r2 = r2.rem_euclid(m2);
+        let (g, p, _) = extended_gcd(m1, m2);
+        if (r2 - r1) % g != 0 {
+            return None;
+        }
+        let lcm = (m1 / g).checked_mul(m2)?;
+        let k = ((r2 - r1) / g % (m2 / g))
+            .checked_mul(p)?
+            .rem_euclid(m2 / g);
+        Some((r1.checked_add(m1.checked_mul(k)?)?.rem_euclid(lcm), lcm))
+    })
+}
+
+/// Number of ways to beat a boat race record: integers `hold` in `0..=time` such that
+/// `hold * (time - hold) > record`.
+///
+/// Exact for all `u128` inputs: the distance grows with `hold` up to `time / 2`, so the first
+/// winning hold is found by binary search, and the winning holds are symmetric around it.
+pub fn race_wins(time: u128, record: u128) -> u128 {
+    if !race_beats(time, record, time / 2) {
+        return 0;
+    }
+    let (mut lo, mut hi) = (0, time / 2); // lo loses, hi wins
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if race_beats(time, record, mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    time - 2 * hi + 1
+}
+
+/// Brute force over all hold durations, the baseline for [`race_wins`].
+pub fn race_wins_slow(time: u128, record: u128) -> u128 {
+    (0..=time)
+        .filter(|&hold| race_beats(time, record, hold))
+        .count() as u128
+}
+
+/// Whether holding for `hold` beats the record, without overflowing: `a * b > d` iff `b > d / a`.
+fn race_beats(time: u128, record: u128, hold: u128) -> bool {
+    hold != 0 && time - hold > record / hold
+}
+
+/// Deterministic Miller–Rabin primality test (valid for all `u64`).
+pub fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    for p in BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'bases: for a in BASES {
+        let mut x = pow_mod_nonzero(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mod_mul(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Prime factors of `n` (with multiplicity, in ascending order), using Pollard's rho algorithm.
+///
+/// There are no prime factors for 0 and 1.
+pub fn factorize(n: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    if n == 0 {
+        return factors;
+    }
+    let mut n = n;
+    for p in [2, 3, 5] {
+        while n > 1 && n.is_multiple_of(p) {
+            factors.push(p);
+            n /= p;
+        }
+    }
+    let mut stack = vec![n];
+    while let Some(n) = stack.pop() {
+        if n == 1 {
+            continue;
+        }
+        if is_prime(n) {
+            factors.push(n);
+            continue;
+        }
+        let d = pollard_rho(n);
+        stack.push(d);
+        stack.push(n / d);
+    }
+    factors.sort_unstable();
+    factors
+}
+
+/// Finds a non-trivial divisor of a composite odd `n`.
+#[allow(clippy::cast_possible_truncation)] // f(x) is < n
+#[allow(clippy::many_single_char_names)]
+fn pollard_rho(n: u64) -> u64 {
+    for c in 1..u128::from(n) {
+        let f = |x: u64| ((u128::from(x) * u128::from(x) + c) % u128::from(n)) as u64;
+        let (mut x, mut y, mut d) = (2, 2, 1);
+        while d == 1 {
+            x = f(x);
+            y = f(f(y));
+            d = gcd(x.abs_diff(y), n);
+        }
+        if d != n {
+            return d;
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn trial_division(n: u64) -> Vec<u64> {
+        let mut factors = Vec::new();
+        let mut n = n;
+        let mut f = 2;
+        while f * f <= n {
+            while n.is_multiple_of(f) {
+                factors.push(f);
+                n /= f;
+            }
+            f += 1;
+        }
+        if n > 1 {
+            factors.push(n);
+        }
+        factors
+    }
+
+    #[test]
+    fn number_theory_works() {
+        assert_eq!(lcm_all([4, 6, 10]), Some(60));
+        assert_eq!(lcm_all([1 << 32, (1 << 32) + 1]), None);
+        assert_eq!(gcd_all([12, 18, 30]), 6);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(2, 4), None);
+        assert_eq!(mod_pow(2, 10, 1000), Some(24));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert!(is_prime(1_000_000_007));
+        assert!(!is_prime(3_215_031_751)); // strong pseudoprime to bases 2, 3, 5, 7
+        assert_eq!(factorize(600_851_475_143), vec![71, 839, 1471, 6857]);
+        assert_eq!(
+            factorize(1_000_000_007 * 998_244_353),
+            vec![998_244_353, 1_000_000_007]
+        );
+        assert_eq!(race_wins(7, 9), 4);
+        assert_eq!(race_wins(30, 200), 9);
+        assert_eq!(race_wins(10, 25), 0); // the best hold only ties the record
+        assert_eq!(race_wins(10, 24), 1);
+        assert_eq!(
+            race_wins(u64::MAX.into(), u64::MAX.into()),
+            u128::from(u64::MAX - 3)
+        );
+        assert_eq!(race_wins(u128::MAX, u128::MAX), u128::MAX - 3);
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_and_lcm_divide(a in 1..1_000_000u64, b in 1..1_000_000u64) {
+            let (g, l) = (gcd(a, b), lcm(a, b).unwrap());
+            prop_assert!(a % g == 0 && b % g == 0);
+            prop_assert!(l % a == 0 && l % b == 0);
+            prop_assert_eq!(g * l, a * b);
+        }
+
+        #[test]
+        fn extended_gcd_is_bezout(a in -1_000_000..1_000_000i128, b in -1_000_000..1_000_000i128) {
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert_eq!(a * x + b * y, g);
+            let (a, b) = (u64::try_from(a.abs()).unwrap(), u64::try_from(b.abs()).unwrap());
+            prop_assert_eq!(g, i128::from(gcd(a, b)));
+        }
+
+        #[test]
+        fn crt_solves_congruences(x in 0..1_000_000i128, m1 in 1..1000i128, m2 in 1..1000i128) {
+            let (r, m) = crt(&[(x % m1, m1), (x % m2, m2)]).unwrap();
+            let (m1, m2) = (u64::try_from(m1).unwrap(), u64::try_from(m2).unwrap());
+            prop_assert_eq!(m, i128::from(lcm(m1, m2).unwrap()));
+            prop_assert_eq!(r, x % m);
+        }
+
+        #[test]
+        fn lcm_overflows_to_none(a in 1..u64::MAX, b in 1..u64::MAX) {
+            let exact = u128::from(a / gcd(a, b)) * u128::from(b);
+            prop_assert_eq!(lcm(a, b), u64::try_from(exact).ok());
+        }
+
+        #[test]
+        fn crt_overflows_to_none(m1 in 1..i128::MAX, m2 in 1..i128::MAX) {
+            let (g, _, _) = extended_gcd(m1, m2);
+            let exact = (m1 / g).unsigned_abs().checked_mul(m2.unsigned_abs());
+            let lcm = exact.and_then(|lcm| i128::try_from(lcm).ok());
+            prop_assert_eq!(crt(&[(0, m1), (0, m2)]), lcm.map(|lcm| (0, lcm)));
+        }
+
+        #[test]
+        fn zero_moduli_are_rejected(r in any::<i128>(), base in any::<u64>(), exp in any::<u64>()) {
+            prop_assert_eq!(crt(&[(r, 0)]), None);
+            prop_assert_eq!(crt(&[(1, 3), (r, 0)]), None);
+            prop_assert_eq!(mod_pow(base, exp, 0), None);
+        }
+
+        #[test]
+        fn race_wins_matches_brute_force(time in 0..2000u128, record in 0..1_100_000u128) {
+            prop_assert_eq!(race_wins(time, record), race_wins_slow(time, record));
+        }
+
+        #[test]
+        fn race_wins_has_exact_bounds(t in any::<u128>(), d in any::<u128>()) {
+            let wins = race_wins(t, d);
+            let beats = |hold: u128| hold.checked_mul(t - hold).is_none_or(|dist| dist > d);
+            if wins == 0 {
+                prop_assert!(!beats(t / 2));
+            } else {
+                let first = (t + 1 - wins) / 2;
+                prop_assert!(beats(first) && !beats(first - 1));
+                prop_assert!(beats(t - first) && !beats(t - first + 1));
+            }
+        }
+
+        #[test]
+        fn factorize_matches_trial_division(n in 1..10_000_000u64) {
+            prop_assert_eq!(factorize(n), trial_division(n));
+            prop_assert_eq!(is_prime(n), trial_division(n) == vec![n]);
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut, Mul};
+
+use itertools::Itertools;
+
+use crate::utils::rational::Rational;
+
+/// Dense matrix of exact rationals, stored row by row.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<Rational>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![Rational::ZERO; rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = Rational::ONE;
+        }
+        m
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the rows have different lengths.
+    pub fn from_rows<T: Copy + Into<Rational>>(rows: &[Vec<T>]) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|row| row.len() == cols), "Ragged rows");
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.iter().flatten().map(|&v| v.into()).collect(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, r: usize) -> &[Rational] {
+        &self.data[r * self.cols..(r + 1) * self.cols]
+    }
+
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let mut t = Self::zeros(self.cols, self.rows);
+        for (r, c) in (0..self.rows).cartesian_product(0..self.cols) {
+            t[(c, r)] = self[(r, c)];
+        }
+        t
+    }
+
+    /// Same matrix with `other` appended to the right.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the row counts differ.
+    #[must_use]
+    pub fn augment(&self, other: &Matrix) -> Self {
+        assert_eq!(self.rows, other.rows, "Row count mismatch");
+        let rows = (0..self.rows)
+            .map(|r| {
+                self.row(r)
+                    .iter()
+                    .chain(other.row(r))
+                    .copied()
+                    .collect_vec()
+            })
+            .collect_vec();
+        let mut m = Self::from_rows(&rows);
+        m.cols = self.cols + other.cols; // in case there are no rows
+        m
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for c in 0..self.cols {
+            self.data.swap(a * self.cols + c, b * self.cols + c);
+        }
+    }
+
+    /// Reduced row echelon form, with the pivot column of each non-zero row.
+    pub fn rref(&self) -> (Matrix, Vec<usize>) {
+        let mut m = self.clone();
+        let mut pivots = vec![];
+        for col in 0..m.cols {
+            let r = pivots.len();
+            let Some(pivot_row) = (r..m.rows).find(|&row| !m[(row, col)].is_zero()) else {
+                continue;
+            };
+            m.swap_rows(r, pivot_row);
+            let inv = m[(r, col)].recip();
+            for c in col..m.cols {
+                m[(r, c)] *= inv;
+            }
+            for row in (0..m.rows).filter(|&row| row != r) {
+                let factor = m[(row, col)];
+                if !factor.is_zero() {
+                    for c in col..m.cols {
+                        let delta = factor * m[(r, c)];
+                        m[(row, c)] -= delta;
+                    }
+                }
+            }
+            pivots.push(col);
+        }
+        (m, pivots)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    /// Basis of the solutions of `self · x = 0`, one vector per free column.
+    pub fn nullspace(&self) -> Vec<Vec<Rational>> {
+        let (rref, pivots) = self.rref();
+        (0..self.cols)
+            .filter(|col| !pivots.contains(col))
+            .map(|free| {
+                let mut v = vec![Rational::ZERO; self.cols];
+                v[free] = Rational::ONE;
+                for (r, &pivot) in pivots.iter().enumerate() {
+                    v[pivot] = -rref[(r, free)];
+                }
+                v
+            })
+            .collect()
+    }
+
+    /// A solution of `self · x = b` (with free variables set to zero), if there is any.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `b` does not have one entry per row.
+    pub fn solve(&self, b: &[Rational]) -> Option<Vec<Rational>> {
+        assert_eq!(b.len(), self.rows, "Right-hand side size mismatch");
+        let rhs = Self {
+            rows: self.rows,
+            cols: 1,
+            data: b.to_vec(),
+        };
+        let (rref, pivots) = self.augment(&rhs).rref();
+        if pivots.last() == Some(&self.cols) {
+            return None; // 0 = 1
+        }
+        let mut x = vec![Rational::ZERO; self.cols];
+        for (r, &pivot) in pivots.iter().enumerate() {
+            x[pivot] = rref[(r, self.cols)];
+        }
+        Some(x)
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the matrix is not square.
+    pub fn determinant(&self) -> Rational {
+        assert_eq!(self.rows, self.cols, "Not a square matrix");
+        let mut m = self.clone();
+        let mut det = Rational::ONE;
+        for col in 0..m.cols {
+            let Some(pivot_row) = (col..m.rows).find(|&row| !m[(row, col)].is_zero()) else {
+                return Rational::ZERO;
+            };
+            if pivot_row != col {
+                m.swap_rows(col, pivot_row);
+                det = -det;
+            }
+            let pivot = m[(col, col)];
+            det *= pivot;
+            for row in col + 1..m.rows {
+                let factor = m[(row, col)] / pivot;
+                for c in col..m.cols {
+                    let delta = factor * m[(col, c)];
+                    m[(row, c)] -= delta;
+                }
+            }
+        }
+        det
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = Rational;
+    fn index(&self, (r, c): (usize, usize)) -> &Rational {
+        &self.data[r * self.cols + c]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut Rational {
+        &mut self.data[r * self.cols + c]
+    }
+}
+
+impl Mul for &Matrix {
+    type Output = Matrix;
+    fn mul(self, rhs: &Matrix) -> Matrix {
+        assert_eq!(self.cols, rhs.rows, "Dimension mismatch");
+        let mut m = Matrix::zeros(self.rows, rhs.cols);
+        for (r, c) in (0..self.rows).cartesian_product(0..rhs.cols) {
+            m[(r, c)] = (0..self.cols).map(|k| self[(r, k)] * rhs[(k, c)]).sum();
+        }
+        m
+    }
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for r in 0..self.rows {
+            writeln!(f, "{}", self.row(r).iter().join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matrix_works() {
+        let m = Matrix::from_rows(&[vec![2_i64, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]]);
+        assert_eq!(m.determinant(), Rational::from(-1_i64));
+        assert_eq!(m.rank(), 3);
+        let b = [8_i64, -11, -3].map(Rational::from);
+        let x = m.solve(&b).unwrap();
+        assert_eq!(x, [2_i64, 3, -1].map(Rational::from));
+        assert_eq!(&m * &Matrix::identity(3), m);
+        assert_eq!(m.transpose().determinant(), m.determinant());
+    }
+
+    #[test]
+    fn singular_matrix_works() {
+        let m = Matrix::from_rows(&[vec![1_i64, 2, 3], vec![2, 4, 6], vec![1, 0, 1]]);
+        assert_eq!(m.determinant(), Rational::ZERO);
+        assert_eq!(m.rank(), 2);
+        let nullspace = m.nullspace();
+        assert_eq!(nullspace.len(), 1);
+        let v = Matrix::from_rows(&nullspace).transpose();
+        assert_eq!(&m * &v, Matrix::zeros(3, 1));
+        assert_eq!(m.solve(&[1_i64, 3, 0].map(Rational::from)), None);
+        assert!(m.solve(&[1_i64, 2, 0].map(Rational::from)).is_some());
+    }
+}
@@ -1,3 +1,19 @@
-pub mod f64_conversions;
+pub mod cycle;
+pub mod dsu;
+pub mod geometry;
+pub mod graph;
+pub mod graph_export;
 pub mod grid;
+pub mod grid3;
+pub mod grid_image;
+pub mod hyperrect;
+pub mod interval;
+pub mod kdtree;
+pub mod lp;
+pub mod math;
+pub mod matrix;
+pub mod memo;
 pub mod point3;
+pub mod poly;
+pub mod pulse_network;
+pub mod rational;
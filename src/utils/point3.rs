@@ -1,5 +1,7 @@
 use std::fmt::{Display, Formatter};
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, AddAssign, Index, Mul, Neg, Sub, SubAssign};
+
+use itertools::iproduct;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Point3<T> {
@@ -7,6 +9,11 @@ pub struct Point3<T> {
     pub y: T,
     pub z: T,
 }
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
 impl<T> Point3<T>
 where
     T: Copy + Sub<Output = T> + Mul<Output = T> + Add<Output = T>,
@@ -17,9 +24,188 @@ where
         let z = p.z - self.z;
         (x * x) + (y * y) + (z * z)
     }
+
+    pub fn dot(&self, p: &Self) -> T {
+        self.x * p.x + self.y * p.y + self.z * p.z
+    }
+
+    #[must_use]
+    pub fn cross(&self, p: &Self) -> Self {
+        Self {
+            x: self.y * p.z - self.z * p.y,
+            y: self.z * p.x - self.x * p.z,
+            z: self.x * p.y - self.y * p.x,
+        }
+    }
+}
+impl<T> Point3<T>
+where
+    T: Copy + Ord + Sub<Output = T> + Add<Output = T>,
+{
+    pub fn manhattan(&self, p: &Self) -> T {
+        let diff = |a: T, b: T| a.max(b) - a.min(b);
+        diff(self.x, p.x) + diff(self.y, p.y) + diff(self.z, p.z)
+    }
+}
+impl<T> Point3<T>
+where
+    T: Copy + Add<Output = T> + From<i8>,
+{
+    /// The 6 face-adjacent points.
+    pub fn neighbors6(&self) -> [Self; 6] {
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .map(|(x, y, z)| *self + Self::new(T::from(x), T::from(y), T::from(z)))
+    }
+
+    /// The 26 points of the surrounding 3x3x3 cube.
+    pub fn neighbors26(&self) -> Vec<Self> {
+        iproduct!(-1..=1, -1..=1, -1..=1)
+            .filter(|&d| d != (0, 0, 0))
+            .map(|(x, y, z)| *self + Self::new(T::from(x), T::from(y), T::from(z)))
+            .collect()
+    }
+}
+impl<T: Copy> From<[T; 3]> for Point3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Self { x, y, z }
+    }
+}
+impl<T> Index<usize> for Point3<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Invalid axis: {index}"),
+        }
+    }
+}
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
 }
 impl<T: Display> Display for Point3<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "({},{},{})", self.x, self.y, self.z)
     }
 }
+
+/// Axis-aligned bounding box, with inclusive bounds.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Aabb<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+impl<T: Copy + Ord> Aabb<T> {
+    /// Smallest box containing all the points, if any.
+    pub fn from_points<I: IntoIterator<Item = Point3<T>>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut aabb = Self {
+            min: first,
+            max: first,
+        };
+        for p in points {
+            aabb.extend(&p);
+        }
+        Some(aabb)
+    }
+
+    pub fn extend(&mut self, p: &Point3<T>) {
+        self.min = Point3::new(
+            self.min.x.min(p.x),
+            self.min.y.min(p.y),
+            self.min.z.min(p.z),
+        );
+        self.max = Point3::new(
+            self.max.x.max(p.x),
+            self.max.y.max(p.y),
+            self.max.z.max(p.z),
+        );
+    }
+
+    pub fn contains(&self, p: &Point3<T>) -> bool {
+        (0..3).all(|axis| self.min[axis] <= p[axis] && p[axis] <= self.max[axis])
+    }
+
+    /// Same box, grown by `margin` on every side.
+    #[must_use]
+    pub fn expand(&self, margin: T) -> Self
+    where
+        T: Add<Output = T> + Sub<Output = T>,
+    {
+        let m = Point3::new(margin, margin, margin);
+        Self {
+            min: self.min - m,
+            max: self.max + m,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point3_works() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(4, -5, 6);
+        assert_eq!(a + b, Point3::new(5, -3, 9));
+        assert_eq!(a - b, Point3::new(-3, 7, -3));
+        assert_eq!(-a * 2, Point3::new(-2, -4, -6));
+        assert_eq!(a.dot(&b), 4 - 10 + 18);
+        assert_eq!(a.cross(&b), Point3::new(27, 6, -13));
+        assert_eq!(a.cross(&b).dot(&a), 0);
+        assert_eq!(a.manhattan(&b), 3 + 7 + 3);
+        assert_eq!(a.neighbors6().len(), 6);
+        assert!(a.neighbors26().iter().all(|n| n.manhattan(&a) <= 3));
+
+        let aabb = Aabb::from_points([a, b, Point3::new(0, 0, 0)]).unwrap();
+        assert_eq!(aabb.min, Point3::new(0, -5, 0));
+        assert_eq!(aabb.max, Point3::new(4, 2, 6));
+        assert!(aabb.contains(&Point3::new(2, 0, 3)) && !aabb.contains(&Point3::new(5, 0, 3)));
+        assert!(aabb.expand(1).contains(&Point3::new(5, 0, 3)));
+    }
+}
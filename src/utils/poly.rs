@@ -0,0 +1,140 @@
+use anyhow::{Result, ensure};
+use itertools::Itertools;
+
+use crate::utils::rational::Rational;
+
+/// Integer-valued polynomial through samples `y(0), y(1), …`, in Newton forward-difference form.
+///
+/// Evaluation is exact, even far away from the samples.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NewtonPoly {
+    /// `Δ^k y(0)` for `k` in `0..=degree`.
+    diffs: Vec<i128>,
+}
+
+impl NewtonPoly {
+    /// Lowest-degree polynomial through the samples.
+    ///
+    /// The degree is the first level at which the differences vanish; if they never do, all
+    /// samples are used (degree `len - 1`).
+    pub fn fit(ys: &[i128]) -> Result<Self> {
+        ensure!(!ys.is_empty(), "No samples");
+        let levels = difference_levels(ys);
+        let degree = levels
+            .iter()
+            .position(|level| level.iter().all(|&d| d == 0))
+            .map_or(ys.len() - 1, |zero_level| zero_level.saturating_sub(1));
+        Ok(Self::from_levels(&levels, degree))
+    }
+
+    /// Polynomial of the given degree through the samples, checking that extra samples agree.
+    pub fn fit_with_degree(ys: &[i128], degree: usize) -> Result<Self> {
+        ensure!(
+            ys.len() > degree,
+            "{} samples are not enough for degree {}",
+            ys.len(),
+            degree
+        );
+        let levels = difference_levels(ys);
+        if let Some(level) = levels.get(degree + 1) {
+            ensure!(
+                level.iter().all(|&d| d == 0),
+                "Samples are not consistent with degree {}",
+                degree
+            );
+        }
+        Ok(Self::from_levels(&levels, degree))
+    }
+
+    fn from_levels(levels: &[Vec<i128>], degree: usize) -> Self {
+        let mut diffs = levels[..=degree].iter().map(|level| level[0]).collect_vec();
+        while diffs.len() > 1 && diffs.last() == Some(&0) {
+            diffs.pop();
+        }
+        Self { diffs }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.diffs.len() - 1
+    }
+
+    /// Value at `x`, using `y(x) = Σ Δ^k y(0) · C(x, k)`.
+    pub fn eval(&self, x: i128) -> i128 {
+        let mut binomial = 1; // C(x, k), an integer even for negative x
+        let mut sum = 0;
+        for (k, &diff) in (0..).zip(&self.diffs) {
+            if k > 0 {
+                binomial = binomial * (x - k + 1) / k;
+            }
+            sum += diff * binomial;
+        }
+        sum
+    }
+}
+
+/// Successive forward differences, starting with the samples themselves.
+fn difference_levels(ys: &[i128]) -> Vec<Vec<i128>> {
+    let mut levels = vec![ys.to_vec()];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| b - a)
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Value at `x` of the polynomial through the given `(x, y)` points, which need not be equally
+/// spaced.
+///
+/// # Panics
+///
+/// Will panic if two points share the same `x`.
+pub fn lagrange(points: &[(i128, i128)], x: i128) -> Rational {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &(xi, yi))| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &(xj, _))| Rational::new(x - xj, xi - xj))
+                .product::<Rational>()
+                * Rational::from(yi)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newton_works() {
+        // 3x² - 2x + 7
+        let f = |x: i128| 3 * x * x - 2 * x + 7;
+        let ys = (0..6).map(f).collect_vec();
+        let poly = NewtonPoly::fit(&ys).unwrap();
+        assert_eq!(poly.degree(), 2);
+        assert_eq!(poly.eval(-1), f(-1));
+        assert_eq!(poly.eval(26_501_365), f(26_501_365));
+        assert_eq!(NewtonPoly::fit_with_degree(&ys[..3], 2).unwrap(), poly);
+        assert!(NewtonPoly::fit_with_degree(&ys, 1).is_err());
+        assert!(NewtonPoly::fit_with_degree(&ys[..2], 2).is_err());
+
+        assert_eq!(NewtonPoly::fit(&[5, 5, 5]).unwrap().degree(), 0);
+        assert_eq!(NewtonPoly::fit(&[0, 1, 8, 27]).unwrap().eval(4), 64);
+    }
+
+    #[test]
+    fn lagrange_works() {
+        let points = [(-2, 23), (1, 8), (5, 72)]; // 3x² - 2x + 7
+        assert_eq!(lagrange(&points, 10), Rational::from(287_i128));
+        assert_eq!(lagrange(&[(0, 1), (2, 2)], 1), Rational::new(3, 2));
+    }
+}
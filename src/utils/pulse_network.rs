@@ -0,0 +1,197 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::utils::graph::{Graph, NodeId};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Pulse {
+    pub source: NodeId,
+    pub destination: NodeId,
+    pub high: bool,
+}
+
+/// Behaviour (and state) of a node in a [`Network`].
+pub trait Module {
+    /// Handles a pulse, returning the level of the pulse to send to every destination, if any.
+    fn receive(&mut self, pulse: &Pulse) -> Option<bool>;
+}
+
+/// Event-driven simulation of modules sending pulses to each other along the edges of a graph.
+///
+/// Pulses are delivered in the order they are sent. Nodes without a module swallow pulses.
+#[derive(Debug, Clone)]
+pub struct Network<M> {
+    graph: Graph,
+    modules: Vec<Option<M>>,
+    counters: HashMap<(NodeId, NodeId, bool), u64>,
+    rounds: u64,
+}
+
+impl<M: Module> Network<M> {
+    pub fn new<F>(graph: Graph, mut create: F) -> Self
+    where
+        F: FnMut(&Graph, NodeId) -> Option<M>,
+    {
+        let modules = graph.nodes().map(|id| create(&graph, id)).collect();
+        Self {
+            graph,
+            modules,
+            counters: HashMap::new(),
+            rounds: 0,
+        }
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    pub fn module(&self, id: NodeId) -> Option<&M> {
+        self.modules[id as usize].as_ref()
+    }
+
+    /// Number of rounds run so far.
+    pub fn rounds(&self) -> u64 {
+        self.rounds
+    }
+
+    /// Number of pulses delivered from `source` to `destination` with the given level.
+    pub fn counter(&self, source: NodeId, destination: NodeId, high: bool) -> u64 {
+        self.counters
+            .get(&(source, destination, high))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Number of pulses delivered with the given level.
+    pub fn pulses(&self, high: bool) -> u64 {
+        self.counters
+            .iter()
+            .filter(|((_, _, h), _)| *h == high)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Delivers `pulse` and all the pulses it triggers, calling `observer` on each of them.
+    pub fn run_round<F>(&mut self, pulse: Pulse, mut observer: F)
+    where
+        F: FnMut(&Pulse),
+    {
+        self.rounds += 1;
+        let mut queue = VecDeque::from([pulse]);
+        while let Some(pulse) = queue.pop_front() {
+            observer(&pulse);
+            *self
+                .counters
+                .entry((pulse.source, pulse.destination, pulse.high))
+                .or_default() += 1;
+
+            let Some(module) = &mut self.modules[pulse.destination as usize] else {
+                continue;
+            };
+            if let Some(high) = module.receive(&pulse) {
+                let source = pulse.destination;
+                queue.extend(
+                    self.graph
+                        .successors(source)
+                        .iter()
+                        .map(|&destination| Pulse {
+                            source,
+                            destination,
+                            high,
+                        }),
+                );
+            }
+        }
+    }
+
+    /// Runs rounds triggered by `pulse` until one of them delivers a pulse matching `predicate`,
+    /// and returns the total number of rounds run. Gives up after `max_rounds` rounds.
+    pub fn run_until<P>(&mut self, pulse: Pulse, max_rounds: u64, mut predicate: P) -> Option<u64>
+    where
+        P: FnMut(&Pulse) -> bool,
+    {
+        for _ in 0..max_rounds {
+            let mut found = false;
+            self.run_round(pulse, |p| found |= predicate(p));
+            if found {
+                return Some(self.rounds);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Forwards pulses, inverted.
+    struct Inverter;
+    impl Module for Inverter {
+        fn receive(&mut self, pulse: &Pulse) -> Option<bool> {
+            Some(!pulse.high)
+        }
+    }
+
+    /// Emits a high pulse every `period` low pulses.
+    struct Divider {
+        period: u32,
+        count: u32,
+    }
+    impl Module for Divider {
+        fn receive(&mut self, pulse: &Pulse) -> Option<bool> {
+            if pulse.high {
+                return None;
+            }
+            self.count += 1;
+            Some(self.count.is_multiple_of(self.period))
+        }
+    }
+
+    enum Node {
+        Inverter(Inverter),
+        Divider(Divider),
+    }
+    impl Module for Node {
+        fn receive(&mut self, pulse: &Pulse) -> Option<bool> {
+            match self {
+                Node::Inverter(m) => m.receive(pulse),
+                Node::Divider(m) => m.receive(pulse),
+            }
+        }
+    }
+
+    #[test]
+    fn network_works() {
+        let mut graph = Graph::new();
+        let (start, div) = graph.add_edge("start", "div");
+        let (_, inv) = graph.add_edge("div", "inv");
+        let (_, out) = graph.add_edge("inv", "out");
+        let mut network = Network::new(graph, |_, id| match id {
+            _ if id == div => Some(Node::Divider(Divider {
+                period: 3,
+                count: 0,
+            })),
+            _ if id == inv => Some(Node::Inverter(Inverter)),
+            _ => None,
+        });
+
+        let press = Pulse {
+            source: start,
+            destination: div,
+            high: false,
+        };
+        let mut delivered = vec![];
+        network.run_round(press, |p| delivered.push(*p));
+        assert_eq!(delivered.len(), 3);
+        assert!(delivered[2].destination == out && delivered[2].high);
+
+        let is_low_out = |p: &Pulse| p.destination == out && !p.high;
+        assert_eq!(network.run_until(press, 10, is_low_out), Some(3));
+        assert_eq!(network.run_until(press, 10, is_low_out), Some(6));
+        assert_eq!(network.run_until(press, 2, is_low_out), None);
+        assert_eq!(network.counter(inv, out, false), 2);
+        assert_eq!(network.counter(inv, out, true), 6);
+        assert_eq!(network.pulses(false), 8 + 2 + 6);
+        assert_eq!(network.pulses(true), 2 + 6);
+    }
+}
@@ -0,0 +1,301 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::utils::math::extended_gcd;
+
+/// Exact fraction over `i128`, always in lowest terms with a positive denominator.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { num: 0, den: 1 };
+    pub const ONE: Self = Self { num: 1, den: 1 };
+
+    /// # Panics
+    ///
+    /// Will panic if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert_ne!(den, 0, "Zero denominator");
+        let (g, _, _) = extended_gcd(num, den);
+        let sign = den.signum();
+        Self {
+            num: sign * num / g,
+            den: sign * den / g,
+        }
+    }
+
+    pub fn numer(self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.den == 1
+    }
+
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+
+    /// Largest integer less than or equal to the value.
+    pub fn floor(self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+
+    #[must_use]
+    pub fn abs(self) -> Self {
+        Self {
+            num: self.num.abs(),
+            den: self.den,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the value is zero.
+    #[must_use]
+    pub fn recip(self) -> Self {
+        Self::new(self.den, self.num)
+    }
+
+    /// Sum, or `None` if it overflows (even in lowest terms).
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let g = gcd(self.den, rhs.den);
+        let num = self
+            .num
+            .checked_mul(rhs.den / g)?
+            .checked_add(rhs.num.checked_mul(self.den / g)?)?;
+        Some(Self::new(num, (self.den / g).checked_mul(rhs.den)?))
+    }
+
+    /// Product, or `None` if it overflows (even in lowest terms).
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // cross-reducing keeps the result in lowest terms
+        let (g1, g2) = (gcd(self.num, rhs.den), gcd(rhs.num, self.den));
+        Some(Self {
+            num: (self.num / g1).checked_mul(rhs.num / g2)?,
+            den: (self.den / g2).checked_mul(rhs.den / g1)?,
+        })
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    extended_gcd(a, b).0
+}
+
+/// Compares `a / b` and `c / d` (with positive denominators) through their continued fractions,
+/// which cannot overflow.
+fn cmp_fractions((mut a, mut b): (i128, i128), (mut c, mut d): (i128, i128)) -> Ordering {
+    let mut reversed = false;
+    loop {
+        let (q1, r1) = (a.div_euclid(b), a.rem_euclid(b));
+        let (q2, r2) = (c.div_euclid(d), c.rem_euclid(d));
+        let ordering = match (q1.cmp(&q2), r1, r2) {
+            (Ordering::Equal, 0, 0) => Ordering::Equal,
+            (Ordering::Equal, 0, _) => Ordering::Less,
+            (Ordering::Equal, _, 0) => Ordering::Greater,
+            // same integer part: the larger fractional part has the smaller reciprocal
+            (Ordering::Equal, _, _) => {
+                (a, b, c, d) = (b, r1, d, r2);
+                reversed = !reversed;
+                continue;
+            }
+            (ordering, _, _) => ordering,
+        };
+        return if reversed {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(num: i128) -> Self {
+        Self { num, den: 1 }
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(num: i64) -> Self {
+        Self::from(i128::from(num))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let g = gcd(self.den, other.den);
+        match (
+            self.num.checked_mul(other.den / g),
+            other.num.checked_mul(self.den / g),
+        ) {
+            (Some(lhs), Some(rhs)) => lhs.cmp(&rhs),
+            _ => cmp_fractions((self.num, self.den), (other.num, other.den)),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).expect("Rational addition overflows")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs)
+            .expect("Rational multiplication overflows")
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.recip()
+    }
+}
+
+impl AddAssign for Rational {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Rational {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Rational {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Rational {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Sum for Rational {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Product for Rational {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rational_works() {
+        let half = Rational::new(2, 4);
+        let third = Rational::new(-1, -3);
+        assert_eq!((half.numer(), half.denom()), (1, 2));
+        assert_eq!(half + third, Rational::new(5, 6));
+        assert_eq!(half - third, Rational::new(1, 6));
+        assert_eq!(half * third, Rational::new(1, 6));
+        assert_eq!(half / third, Rational::new(3, 2));
+        assert_eq!(Rational::new(3, -6), -half);
+        assert!(third < half && -half < third);
+        assert_eq!(Rational::new(-7, 2).floor(), -4);
+        assert_eq!((half + half).to_integer(), Some(1));
+        assert_eq!(half.to_integer(), None);
+        assert_eq!(
+            [half, third, third]
+                .into_iter()
+                .sum::<Rational>()
+                .to_string(),
+            "7/6"
+        );
+    }
+
+    #[test]
+    fn rational_avoids_overflows() {
+        // the common factors of the denominators would overflow the cross products
+        let (a, b) = (Rational::new(1, 3 << 64), Rational::new(1, 5 << 64));
+        assert_eq!(a + b, Rational::new(8, 15 << 64));
+        assert!(b < a);
+        assert_eq!(
+            Rational::new(1 << 100, 3) * Rational::new(5, 1 << 100),
+            Rational::new(5, 3)
+        );
+
+        let (big, bigger) = (
+            Rational::new(i128::MAX, i128::MAX - 1),
+            Rational::new(i128::MAX - 1, i128::MAX - 2),
+        );
+        assert!(big < bigger && -bigger < -big);
+        assert_eq!(big.cmp(&big), Ordering::Equal);
+
+        let max = Rational::from(i128::MAX);
+        assert_eq!(max.checked_add(Rational::ONE), None);
+        assert_eq!(
+            max.checked_mul(Rational::new(1, 2)),
+            Some(Rational::new(i128::MAX, 2))
+        );
+        assert_eq!(max.checked_mul(Rational::from(2i128)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Rational multiplication overflows")]
+    fn rational_overflow_panics() {
+        let _: Rational = Rational::from(i128::MAX) * Rational::from(2i128);
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Result, anyhow};
+use indexmap::IndexMap;
+use itertools::Itertools;
+
+/// Modification times of the watched files, to detect changes by polling.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Snapshot(Vec<(PathBuf, Option<SystemTime>)>);
+
+impl Snapshot {
+    /// Takes the given files and the files in the given directories (missing ones included, so
+    /// that creating or deleting them counts as a change).
+    pub fn take(files: &[PathBuf], dirs: &[PathBuf]) -> Result<Self> {
+        let mut paths = files.to_vec();
+        for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+            for entry in std::fs::read_dir(dir)? {
+                paths.push(entry?.path());
+            }
+        }
+        paths.sort();
+        let times = paths
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+        Ok(Self(times))
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).ok()?.modified().ok()
+}
+
+/// Answers of a run, keyed by label and data (e.g. `year2025::day08::part1 input`).
+pub type Results = IndexMap<String, String>;
+
+/// Reads the answers from the runner's JSON report, marking the wrong ones.
+pub fn parse_report(json: &str) -> Result<Results> {
+    let report: serde_json::Value = serde_json::from_str(json)?;
+    let entries = report
+        .as_array()
+        .ok_or_else(|| anyhow!("The report is not an array"))?;
+    entries
+        .iter()
+        .map(|entry| {
+            let field = |name: &str| {
+                entry[name]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("Missing {name} in report entry {entry}"))
+            };
+            let key = format!("{} {}", field("label")?, field("data")?);
+            let mut answer = field("answer")?.to_owned();
+            if entry["correct"] == false {
+                answer.push_str(" (wrong)");
+            }
+            Ok((key, answer))
+        })
+        .collect()
+}
+
+/// One line per result that appeared, disappeared or changed between two runs.
+pub fn diff(previous: &Results, current: &Results) -> Vec<String> {
+    let changed = current
+        .iter()
+        .filter_map(|(key, answer)| match previous.get(key) {
+            None => Some(format!("+ {key}: {answer}")),
+            Some(previous) if previous != answer => {
+                Some(format!("~ {key}: {previous} -> {answer}"))
+            }
+            Some(_) => None,
+        });
+    let removed = previous
+        .iter()
+        .filter(|(key, _)| !current.contains_key(*key))
+        .map(|(key, answer)| format!("- {key}: {answer}"));
+    changed.chain(removed).collect_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_works() {
+        let previous = parse_report(
+            r#"[
+                {"label": "year2025::day08::part1", "data": "example", "answer": "20", "correct": true},
+                {"label": "year2025::day08::part1", "data": "input", "answer": "12", "correct": false},
+                {"label": "year2025::day08::part2", "data": "example", "answer": "7", "correct": null}
+            ]"#,
+        )
+        .unwrap();
+        let current = parse_report(
+            r#"[
+                {"label": "year2025::day08::part1", "data": "example", "answer": "20", "correct": true},
+                {"label": "year2025::day08::part1", "data": "input", "answer": "129564", "correct": true},
+                {"label": "year2025::day08::part2", "data": "input", "answer": "42", "correct": null}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            diff(&previous, &current),
+            [
+                "~ year2025::day08::part1 input: 12 (wrong) -> 129564",
+                "+ year2025::day08::part2 input: 42",
+                "- year2025::day08::part2 example: 7",
+            ]
+        );
+        assert!(diff(&current, &current).is_empty());
+    }
+}
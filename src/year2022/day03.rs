@@ -12,6 +12,7 @@ pub fn day() -> Day<u32> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        ..Day::default()
     }
 }
 
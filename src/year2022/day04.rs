@@ -3,6 +3,7 @@ use std::ops::Range;
 use anyhow::{anyhow, Context, Result};
 
 use crate::challenge::Day;
+use crate::utils::interval::IntervalSet;
 
 pub fn day() -> Day<u32> {
     Day {
@@ -12,6 +13,7 @@ pub fn day() -> Day<u32> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        ..Day::default()
     }
 }
 
@@ -42,14 +44,14 @@ fn parse_range(s: &str) -> Result<Range<u32>> {
             .with_context(|| format!("Could not parse value {s}"))
     };
     let (from, to) = s.split_once('-').ok_or(anyhow!("Could not split range"))?;
-    Ok(parse_u32(from)?..parse_u32(to)?)
+    Ok(parse_u32(from)?..parse_u32(to)? + 1)
 }
 
 fn fully_contains(r: &Range<u32>, other: &Range<u32>) -> bool {
-    r.start <= other.start && r.end >= other.end
+    IntervalSet::from(r.clone()).contains_range(other)
 }
 fn overlaps(r: &Range<u32>, other: &Range<u32>) -> bool {
-    r.start <= other.end && r.end >= other.start
+    IntervalSet::from(r.clone()).overlaps_range(other)
 }
 
 fn part2(data: &str) -> Result<u32> {
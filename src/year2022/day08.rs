@@ -10,6 +10,7 @@ pub fn day() -> Day<i32> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        ..Day::default()
     }
 }
 
@@ -0,0 +1,39 @@
+use anyhow::{Result, anyhow};
+use itertools::Itertools;
+
+use crate::challenge::Day;
+use crate::utils::grid3::VoxelSet;
+use crate::utils::point3::Point3;
+
+pub fn day() -> Day<usize> {
+    Day {
+        part1_solutions: (64, Some(4340)),
+        part2_solutions: Some((58, Some(2468))),
+        part1_solver: part1,
+        part2_solver: part2,
+        source_file: file!(),
+        distinct_examples: false,
+        ..Day::default()
+    }
+}
+
+fn part1(data: &str) -> Result<usize> {
+    Ok(parse_cubes(data)?.surface_area())
+}
+
+fn part2(data: &str) -> Result<usize> {
+    Ok(parse_cubes(data)?.exterior_surface_area())
+}
+
+fn parse_cubes(data: &str) -> Result<VoxelSet> {
+    data.lines()
+        .map(|line| {
+            let (x, y, z) = line
+                .split(',')
+                .map(str::parse)
+                .collect_tuple()
+                .ok_or_else(|| anyhow!("Invalid cube: {line}"))?;
+            Ok(Point3::new(x?, y?, z?))
+        })
+        .collect()
+}
@@ -7,6 +7,7 @@ mod day04;
 mod day05;
 mod day06;
 mod day07;
+mod day18;
 
 #[must_use]
 pub fn challenge_days() -> Vec<DayWrapper> {
@@ -18,5 +19,6 @@ pub fn challenge_days() -> Vec<DayWrapper> {
         day05::day().into(),
         day06::day().into(),
         day07::day().into(),
+        day18::day().into(),
     ]
 }
@@ -7,6 +7,7 @@ use itertools::Itertools;
 use strum_macros::EnumString;
 
 use crate::challenge::Day;
+use crate::utils::interval::{IntervalSet, RangeMap};
 use crate::year2023::day05::Category::{Location, Seed};
 
 pub fn day() -> Day<u64> {
@@ -17,6 +18,7 @@ pub fn day() -> Day<u64> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        ..Day::default()
     }
 }
 
@@ -38,38 +40,26 @@ fn part1(data: &str) -> Result<u64> {
 
 fn part2(data: &str) -> Result<u64> {
     let puzzle = data.parse::<Puzzle>()?;
-    let seed_ranges = puzzle
+    let seed_ranges: IntervalSet<u64> = puzzle
         .seeds
         .iter()
         .chunks(2)
         .into_iter()
         .map(|chunk| chunk.collect_tuple::<(_, _)>().unwrap())
         .map(|(start, len)| *start..*start + *len)
-        .collect_vec();
+        .collect();
 
     let mut category = &Seed;
-    let mut ranges = seed_ranges.clone();
-
-    loop {
-        let map = puzzle.maps_by_source.get(category);
-        if map.is_none() {
-            break;
-        }
-        let map = map.unwrap();
-
-        let destination_ranges = ranges
-            .iter()
-            .flat_map(|range| map.transform(range))
-            .collect_vec();
+    let mut ranges = seed_ranges;
 
+    while let Some(map) = puzzle.maps_by_source.get(category) {
         category = &map.destination;
-        ranges = destination_ranges;
+        ranges = map.range_map.map_set(&ranges);
     }
 
     assert_eq!(category, &Location);
 
-    let min = ranges.iter().map(|range| range.start).min().unwrap();
-    Ok(min)
+    Ok(ranges.min().unwrap())
 }
 
 #[derive(Debug)]
@@ -84,13 +74,7 @@ impl Puzzle {
     pub fn resolve(&self, item: Item) -> Option<Item> {
         let (number, category) = item;
         let map = self.maps_by_source.get(&category)?;
-        let range = map.ranges.iter().find(|range| {
-            range.source_start <= number && number < range.source_start + range.length
-        });
-        let destination_number = range.map_or(number, |range| {
-            range.destination_start + number - range.source_start
-        });
-        Some((destination_number, map.destination.clone()))
+        Some((map.range_map.map(number), map.destination.clone()))
     }
 
     pub fn resolve_recursively(&self, item: Item) -> Item {
@@ -142,45 +126,7 @@ impl FromStr for Puzzle {
 struct ConversionMap {
     source: Category,
     destination: Category,
-    ranges: Vec<ConversionRange>, // sorted by source_start
-}
-
-impl ConversionMap {
-    pub fn transform(&self, range: &Range<u64>) -> Vec<Range<u64>> {
-        let conversions = self
-            .ranges
-            .iter()
-            .filter_map(|r| r.transform(range).map(|transformed| (r, transformed)))
-            .collect_vec();
-
-        let mut ranges = vec![];
-        let mut pos = range.start;
-
-        for (conv, dest_range) in conversions {
-            let source_range = conv.source_range();
-
-            if let Some(r) = maybe_range(pos, source_range.start) {
-                ranges.push(r);
-            }
-
-            ranges.push(dest_range.clone());
-            pos = source_range.end;
-        }
-
-        if let Some(r) = maybe_range(pos, range.end) {
-            ranges.push(r);
-        }
-
-        ranges
-    }
-}
-
-fn maybe_range(start: u64, end: u64) -> Option<Range<u64>> {
-    if start < end {
-        Some(start..end)
-    } else {
-        None
-    }
+    range_map: RangeMap<u64>,
 }
 
 impl FromStr for ConversionMap {
@@ -196,14 +142,16 @@ impl FromStr for ConversionMap {
         let categories = categories.map(|category| category.parse::<Category>().unwrap());
         let [source, destination] = categories;
 
+        let mut range_map = RangeMap::new();
+        for line in ranges {
+            let range = line.parse::<ConversionRange>()?;
+            range_map.insert(range.source_range(), range.destination_start);
+        }
+
         Ok(ConversionMap {
             source,
             destination,
-            ranges: ranges
-                .iter()
-                .map(|line| line.parse::<ConversionRange>().unwrap())
-                .sorted_by_key(|r| r.source_start)
-                .collect_vec(),
+            range_map,
         })
     }
 }
@@ -219,19 +167,6 @@ impl ConversionRange {
     pub fn source_range(&self) -> Range<u64> {
         self.source_start..self.source_start + self.length
     }
-
-    pub fn transform(&self, range: &Range<u64>) -> Option<Range<u64>> {
-        let intersection_start = range.start.max(self.source_start);
-        let intersection_end = range.end.min(self.source_start + self.length);
-        if intersection_start >= intersection_end {
-            None
-        } else {
-            let diff = i128::from(self.destination_start) - i128::from(self.source_start);
-            let start = u64::try_from(i128::from(intersection_start) + diff).unwrap();
-            let end = u64::try_from(i128::from(intersection_end) + diff).unwrap();
-            Some(start..end)
-        }
-    }
 }
 
 impl FromStr for ConversionRange {
@@ -275,8 +210,10 @@ mod tests {
             source_start: 1147,
             length: 444,
         };
+        let mut range_map = RangeMap::new();
+        range_map.insert(cr.source_range(), cr.destination_start);
         let r: Range<u64> = 1115..1195;
-        let transformed = cr.transform(&r).unwrap();
-        assert_eq!(transformed, 0..48);
+        let transformed = range_map.map_set(&IntervalSet::from(r));
+        assert_eq!(transformed.ranges(), &[0..48, 1115..1147]);
     }
 }
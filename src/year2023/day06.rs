@@ -3,8 +3,8 @@ use std::str::FromStr;
 use anyhow::Result;
 use itertools::Itertools;
 
-use crate::challenge::Day;
-use crate::utils::f64_conversions::{try_f64_from_usize, try_usize_from_f64};
+use crate::challenge::{Day, Part, Variant};
+use crate::utils::math::{race_wins, race_wins_slow};
 
 pub fn day() -> Day<usize> {
     Day {
@@ -14,6 +14,19 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        variants: vec![
+            Variant {
+                part: Part::Part1,
+                name: "slow",
+                solver: part1_slow,
+            },
+            Variant {
+                part: Part::Part2,
+                name: "slow",
+                solver: part2_slow,
+            },
+        ],
+        ..Day::default()
     }
 }
 
@@ -52,58 +65,50 @@ impl FromStr for Puzzle {
     }
 }
 
+type WaysToBeat = fn(&Race) -> Result<usize>;
+
 fn part1(data: &str) -> Result<usize> {
+    part1_with(data, ways_to_beat)
+}
+
+fn part1_slow(data: &str) -> Result<usize> {
+    part1_with(data, ways_to_beat_slow)
+}
+
+fn part1_with(data: &str, ways_to_beat: WaysToBeat) -> Result<usize> {
     let races = data.parse::<Puzzle>()?.races;
 
-    let product = races.iter().map(ways_to_beat).product::<usize>();
+    let product = races.iter().map(ways_to_beat).product::<Result<usize>>()?;
 
     Ok(product)
 }
 
-#[deprecated]
-#[allow(dead_code)]
-fn ways_to_beat_slow(race: &Race) -> usize {
-    (0..=race.time)
-        .map(|hold| {
-            let speed = hold;
-            speed * (race.time - hold) // dist
-        })
-        .filter(|dist| *dist > race.distance)
-        .count()
+fn ways_to_beat_slow(race: &Race) -> Result<usize> {
+    ways_to_beat_with(race, race_wins_slow)
 }
 
-fn ways_to_beat(race: &Race) -> usize {
-    // x: time
-    // y: distance
-    // T = race.time
-    // D = race.distance + 1
-    // we're looking for roots of: - x^2 + T * x - D = 0
-    // i.e. quadratic roots of a=-1, b=T, c=-D
-    // which are: x = (-b +- sqrt(b^2 - 4ac)) / 2a
-    let a = -1.0;
-    let b = try_f64_from_usize(race.time).unwrap();
-    let c = -try_f64_from_usize(race.distance + 1).unwrap();
-    let roots = quadratic_roots(a, b, c);
-    let (x1, x2) = roots;
-    let x1 = try_usize_from_f64(x1.ceil()).unwrap();
-    let x2 = try_usize_from_f64(x2.floor()).unwrap();
-    x2 - x1 + 1
+fn ways_to_beat(race: &Race) -> Result<usize> {
+    ways_to_beat_with(race, race_wins)
 }
 
-fn quadratic_roots(a: f64, b: f64, c: f64) -> (f64, f64) {
-    let discriminant = b.powi(2) - 4.0 * a * c;
-    let root = discriminant.sqrt();
-    let x1 = (-b + root) / (2.0 * a);
-    let x2 = (-b - root) / (2.0 * a);
-    (x1, x2)
+fn ways_to_beat_with(race: &Race, wins: fn(u128, u128) -> u128) -> Result<usize> {
+    let wins = wins(u128::try_from(race.time)?, u128::try_from(race.distance)?);
+    Ok(usize::try_from(wins)?)
 }
 
 fn part2(data: &str) -> Result<usize> {
+    part2_with(data, ways_to_beat)
+}
+
+fn part2_slow(data: &str) -> Result<usize> {
+    part2_with(data, ways_to_beat_slow)
+}
+
+fn part2_with(data: &str, ways_to_beat: WaysToBeat) -> Result<usize> {
     let races = data.parse::<Puzzle>()?.races;
     let time = races.iter().map(|r| r.time).join("").parse::<usize>()?;
     let distance = races.iter().map(|r| r.distance).join("").parse::<usize>()?;
     let race = Race { time, distance };
 
-    let ways_to_beat = ways_to_beat(&race);
-    Ok(ways_to_beat)
+    ways_to_beat(&race)
 }
@@ -6,6 +6,7 @@ use itertools::Itertools;
 use strum_macros::EnumString;
 
 use crate::challenge::Day;
+use crate::utils::math::lcm_all;
 
 pub fn day() -> Day<u64> {
     Day {
@@ -15,6 +16,7 @@ pub fn day() -> Day<u64> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: true,
+        ..Day::default()
     }
 }
 
@@ -64,30 +66,9 @@ fn part2(data: &str) -> Result<u64> {
         .collect_vec();
     let steps = starts
         .iter()
-        .map(|start| solve(&puzzle, start, |node_id| node_id.ends_with('Z')).unwrap())
-        .collect_vec();
-    let unique_factors_product = steps
-        .iter()
-        .flat_map(|&x| prime_factorization_trial_division(x))
-        .unique()
-        .product();
-    Ok(unique_factors_product)
-}
-
-fn prime_factorization_trial_division(x: u64) -> Vec<u64> {
-    // https://en.wikipedia.org/wiki/Trial_division
-    let mut factors: Vec<u64> = Vec::new();
-    let mut f: u64 = 2; // the first possible factor
-    let mut n = x;
-    while n > 1 {
-        if n.is_multiple_of(f) {
-            factors.push(f);
-            n /= f;
-        } else {
-            f += 1;
-        }
-    }
-    factors
+        .map(|start| solve(&puzzle, start, |node_id| node_id.ends_with('Z')))
+        .collect::<Result<Vec<_>>>()?;
+    lcm_all(steps).ok_or_else(|| anyhow!("The least common multiple overflows"))
 }
 
 #[derive(Debug)]
@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use itertools::Itertools;
 
 use crate::challenge::Day;
+use crate::utils::poly::NewtonPoly;
 
 pub fn day() -> Day<i32> {
     Day {
@@ -11,6 +12,7 @@ pub fn day() -> Day<i32> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        ..Day::default()
     }
 }
 
@@ -36,7 +38,7 @@ fn sum_of_next_values(data: &str, side: &Side) -> Result<i32> {
         .collect::<Result<Vec<_>>>()?
         .iter()
         .map(|values| compute_next_value(values, side))
-        .sum();
+        .sum::<Result<_>>()?;
     Ok(sum)
 }
 
@@ -45,19 +47,12 @@ enum Side {
     Left,
 }
 
-fn compute_next_value(values: &[i32], side: &Side) -> i32 {
-    if values.iter().all(|&v| v == 0) {
-        0
-    } else {
-        let next_layer = values
-            .iter()
-            .zip(values.iter().skip(1))
-            .map(|(a, b)| b - a)
-            .collect_vec();
-        let diff = compute_next_value(&next_layer, side);
-        match side {
-            Side::Right => values.last().unwrap() + diff,
-            Side::Left => values.first().unwrap() - diff,
-        }
-    }
+fn compute_next_value(values: &[i32], side: &Side) -> Result<i32> {
+    let ys = values.iter().copied().map(i128::from).collect_vec();
+    let poly = NewtonPoly::fit(&ys)?;
+    let x = match side {
+        Side::Right => i128::try_from(values.len())?,
+        Side::Left => -1,
+    };
+    Ok(i32::try_from(poly.eval(x))?)
 }
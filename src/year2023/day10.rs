@@ -2,10 +2,12 @@ use anyhow::Result;
 use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
 use strum::IntoEnumIterator;
-use strum_macros::{EnumIter, EnumString};
+use strum_macros::EnumString;
 
-use crate::challenge::Day;
+use crate::challenge::{Day, ImageExport};
+use crate::utils::geometry::{Point2, Polygon, RectilinearRegion};
 use crate::utils::grid::{Coord, Direction, Grid};
+use crate::utils::grid_image::Rgb;
 
 pub fn day() -> Day<usize> {
     Day {
@@ -15,6 +17,11 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: true,
+        image_exports: vec![ImageExport {
+            label: "loop",
+            export: loop_image,
+        }],
+        ..Day::default()
     }
 }
 
@@ -25,7 +32,7 @@ fn part1(data: &str) -> Result<usize> {
 
     let start = solver.find_start();
 
-    let (_, (a, b)) = solver.find_firsts(start);
+    let (a, b) = solver.find_firsts(start);
 
     let dist_a = solver.explore(start, &a);
     let dist_b = solver.explore(start, &b);
@@ -44,32 +51,39 @@ fn part1(data: &str) -> Result<usize> {
     Ok(*max_dist)
 }
 
-fn part2_internal(data: &str) -> Result<(Solver, IndexSet<Coord>, Vec<Coord>)> {
+fn part2(data: &str) -> Result<usize> {
     let grid: Grid<Tile> = data.parse()?;
 
     let solver = Solver { grid };
     let start = solver.find_start();
-    let (start_tile, (first, _)) = solver.find_firsts(start);
+    let (first, _) = solver.find_firsts(start);
     let explored = solver.explore(start, &first);
-    let mut loop_coords: IndexSet<_> = explored.into_keys().collect();
-
-    loop_coords.insert(start);
 
-    let final_solver = solver.transform_grid(|(_, &tile)| {
-        use Tile::*;
-        match tile {
-            Start => start_tile,
-            _ => tile,
-        }
-    });
+    // tiles are lattice points, and the loop (in walking order) is a polygon through them
+    let polygon = Polygon::new(explored.keys().chain([&start]).map(to_point).collect());
 
-    let enclosed = final_solver.find_enclosed(&loop_coords);
+    Ok(usize::try_from(polygon.interior_points())?)
+}
 
-    Ok((solver, loop_coords, enclosed))
+/// The loop, and the tiles it encloses.
+fn loop_image(data: &str) -> Result<Vec<Grid<Rgb>>> {
+    let solver = Solver {
+        grid: data.parse()?,
+    };
+    let (loop_coords, enclosed) = solver.loop_and_enclosed();
+    Ok(vec![solver.grid.transform(|(coord, _)| {
+        if loop_coords.contains(coord) {
+            [32, 96, 224]
+        } else if enclosed.contains(coord) {
+            [224, 160, 32]
+        } else {
+            [240, 240, 240]
+        }
+    })])
 }
-fn part2(data: &str) -> Result<usize> {
-    let (_, _, enclosed) = part2_internal(data)?;
-    Ok(enclosed.len())
+
+fn to_point(c: &Coord) -> Point2 {
+    (i64::try_from(c.x()).unwrap(), i64::try_from(c.y()).unwrap())
 }
 
 struct Solver {
@@ -78,14 +92,6 @@ struct Solver {
 
 type Flow = (Coord, Direction);
 impl Solver {
-    pub fn transform_grid<F>(&self, f: F) -> Solver
-    where
-        F: Fn((&Coord, &Tile)) -> Tile,
-    {
-        let grid = self.grid.transform(f);
-        Solver { grid }
-    }
-
     fn find_start(&self) -> Coord {
         let (start,) = self
             .grid
@@ -96,17 +102,11 @@ impl Solver {
         start
     }
 
-    fn find_firsts(&self, start: Coord) -> (Tile, (Flow, Flow)) {
-        let ((dir_a, a), (dir_b, b)) = Direction::iter()
-            .filter_map(|d| self.find_next(start, d).map(|flow| (d, flow)))
-            .collect_tuple()
-            .unwrap();
-        let connections = IndexSet::from([dir_a, dir_b]);
-        let (start_tile,) = Tile::iter()
-            .filter(|t| t.connections() == connections)
+    fn find_firsts(&self, start: Coord) -> (Flow, Flow) {
+        Direction::iter()
+            .filter_map(|d| self.find_next(start, d))
             .collect_tuple()
-            .unwrap();
-        (start_tile, (a, b))
+            .unwrap()
     }
 
     fn find_next(&self, pos: Coord, dir: Direction) -> Option<Flow> {
@@ -146,57 +146,27 @@ impl Solver {
         distances
     }
 
-    fn find_enclosed(&self, loop_coords: &IndexSet<Coord>) -> Vec<Coord> {
-        use Direction::*;
-        let vertical_directions = &IndexSet::from([N, S]);
-
-        self.grid
-            .row_coords()
-            .into_iter()
-            .flat_map(|row_coords| {
-                let mut inside = false;
-                let mut pending_crossing: Option<Direction> = None;
-                row_coords.into_iter().filter(move |coord| {
-                    if loop_coords.contains(coord) {
-                        let connections = self.grid.get(coord).connections();
-
-                        let vertical_connections: IndexSet<_> =
-                            connections.intersection(vertical_directions).collect();
-
-                        let crossing =
-                            match vertical_connections.into_iter().collect_vec().as_slice() {
-                                [_, _] => {
-                                    pending_crossing = None;
-                                    true
-                                }
-                                [vertical_direction] => {
-                                    if let Some(prev_pending_crossing) = pending_crossing {
-                                        let crossing =
-                                            prev_pending_crossing != **vertical_direction;
-                                        pending_crossing = None;
-                                        crossing
-                                    } else {
-                                        pending_crossing = Some(**vertical_direction);
-                                        false
-                                    }
-                                }
-                                [] => false,
-                                _ => unreachable!(),
-                            };
-
-                        if crossing {
-                            inside = !inside;
-                        }
-                        false
-                    } else {
-                        inside
-                    }
-                })
-            })
-            .collect()
+    /// The tiles of the loop, and the tiles it encloses.
+    fn loop_and_enclosed(&self) -> (IndexSet<Coord>, IndexSet<Coord>) {
+        let start = self.find_start();
+        let (first, _) = self.find_firsts(start);
+        let loop_coords: IndexSet<Coord> = self
+            .explore(start, &first)
+            .into_keys()
+            .chain([start])
+            .collect();
+        let region = RectilinearRegion::new(&Polygon::new(
+            loop_coords.iter().map(to_point).collect(),
+        ));
+        let enclosed = self
+            .grid
+            .coords()
+            .filter(|c| !loop_coords.contains(c) && region.contains(to_point(c)))
+            .collect();
+        (loop_coords, enclosed)
     }
 
-    #[allow(dead_code)]
+    #[cfg(test)]
     fn grid_to_string(
         &self,
         loop_coords: &IndexSet<Coord>,
@@ -219,7 +189,7 @@ impl Solver {
     }
 }
 
-#[derive(EnumString, Debug, Eq, PartialEq, strum_macros::Display, Copy, Clone, EnumIter)]
+#[derive(EnumString, Debug, Eq, PartialEq, strum_macros::Display, Copy, Clone)]
 enum Tile {
     #[strum(serialize = "|")]
     NS,
@@ -290,18 +260,21 @@ mod tests {
     #[test]
     fn part2_extra_examples() -> Result<()> {
         let f_common = |s: &str, keep_non_enclosed: bool| {
-            part2_internal(s).map(|(solver, loop_coords, enclosed)| {
-                let enclosed = IndexSet::from_iter(enclosed);
-                (
-                    enclosed.len(),
-                    solver.grid_to_string(&loop_coords, &enclosed, keep_non_enclosed),
-                )
-            })
+            let data = trim_lines(s);
+            let solver = Solver {
+                grid: data.parse()?,
+            };
+            let (loop_coords, enclosed) = solver.loop_and_enclosed();
+            assert_eq!(part2(&data)?, enclosed.len());
+            Ok::<_, anyhow::Error>((
+                enclosed.len(),
+                solver.grid_to_string(&loop_coords, &enclosed, keep_non_enclosed),
+            ))
         };
         let f = |s: &str| f_common(s, false);
         let f_keep = |s: &str| f_common(s, true);
         assert_eq!(
-            f(indoc! {"
+            f(r"
                 .F----7F7F7F7F-7....
                 .|F--7||||||||FJ....
                 .||.FJ||||||||L7....
@@ -312,7 +285,7 @@ mod tests {
                 .....|FJLJ|FJ|F7|.LJ
                 ....FJL-7.||.||||...
                 ....L---J.LJ.LJLJ...
-            "})?,
+            ")?,
             (
                 8,
                 indoc! {"
@@ -326,12 +299,12 @@ mod tests {
                     OOOOO|FJLJ|FJ|F7|OLJ
                     OOOOFJL-7O||O||||OOO
                     OOOOL---JOLJOLJLJOOO
-                    "}
+                "}
                 .to_owned()
             )
         );
         assert_eq!(
-            f_keep(indoc! {"
+            f_keep(r"
                 FF7FSF7F7F7F7F7F---7
                 L|LJ||||||||||||F--J
                 FL-7LJLJ||||||LJL-77
@@ -342,7 +315,7 @@ mod tests {
                 7-L-JL7||F7|L7F-7F7|
                 L.L7LFJ|||||FJL7||LJ
                 L7JLJL-JLJLJL--JLJ.L
-            "})?,
+            ")?,
             (
                 10,
                 indoc! {"
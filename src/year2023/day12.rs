@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::iter;
 use std::str::FromStr;
 
@@ -6,7 +5,8 @@ use anyhow::{Context, Result};
 use itertools::Itertools;
 use strum_macros::{EnumIter, EnumString};
 
-use crate::challenge::Day;
+use crate::challenge::{Day, diagnostic};
+use crate::utils::memo::{Memo, Stats, suffix_offset};
 
 pub fn day() -> Day<usize> {
     Day {
@@ -16,35 +16,28 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        ..Day::default()
     }
 }
 
 fn part1(data: &str) -> Result<usize> {
-    let sum = data
-        .lines()
-        .map(|line| {
-            let record: Record = line.parse()?;
-            let arrangements = count_arrangements(&record);
-            Ok(arrangements)
-        })
-        .collect::<Result<Vec<_>>>()?
-        .iter()
-        .sum();
-    Ok(sum)
+    sum_arrangements(data.lines().map(ToOwned::to_owned))
 }
 
 fn part2(data: &str) -> Result<usize> {
-    let sum = data
-        .lines()
-        .map(|line| {
-            let line = expand(line);
-            let record: Record = line.parse()?;
-            let arrangements = count_arrangements(&record);
-            Ok(arrangements)
-        })
-        .collect::<Result<Vec<_>>>()?
-        .iter()
-        .sum();
+    sum_arrangements(data.lines().map(expand))
+}
+
+fn sum_arrangements<I: Iterator<Item = String>>(lines: I) -> Result<usize> {
+    let mut sum = 0;
+    let mut stats = Stats::default();
+    for line in lines {
+        let record: Record = line.parse()?;
+        let mut memo = Memo::new();
+        sum += count_arrangements(&mut memo, &record);
+        stats += memo.stats();
+    }
+    diagnostic(|| format!("cache: {stats}"));
     Ok(sum)
 }
 
@@ -57,19 +50,20 @@ fn expand(line: &str) -> String {
     .join(" ")
 }
 
-fn count_arrangements(record: &Record) -> usize {
-    let mut cached_impl = Cached {
-        cache: HashMap::new(),
-        cache_stats: CacheStats { hit: 0, miss: 0 },
-    };
-    cached_impl.rec(
+// (pattern offset, groups offset, remaining)
+type Key = (usize, usize, Remaining);
+
+fn count_arrangements(memo: &mut Memo<Key, usize>, record: &Record) -> usize {
+    let solver = Solver { record };
+    solver.rec(
+        memo,
         record.pattern.as_slice(),
         record.groups.as_slice(),
         Remaining::Free,
     )
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum Remaining {
     Damaged(usize),
     NonDamaged,
@@ -94,36 +88,32 @@ enum Outcome {
     UseNextGroupOrSkip,
 }
 
-#[derive(Debug)]
-struct CacheStats {
-    hit: usize,
-    miss: usize,
-}
-struct Cached {
-    cache: HashMap<String, usize>,
-    cache_stats: CacheStats,
+struct Solver<'a> {
+    record: &'a Record,
 }
-impl Cached {
-    fn rec(&mut self, pattern: &[Condition], groups: &[usize], remaining: Remaining) -> usize {
-        let key = format!(
-            "{}, {:?}, {:?}",
-            pattern.iter().map(ToString::to_string).join(""),
-            groups,
-            remaining
+impl Solver<'_> {
+    fn rec(
+        &self,
+        memo: &mut Memo<Key, usize>,
+        pattern: &[Condition],
+        groups: &[usize],
+        remaining: Remaining,
+    ) -> usize {
+        let key = (
+            suffix_offset(&self.record.pattern, pattern),
+            suffix_offset(&self.record.groups, groups),
+            remaining,
         );
-
-        if let Some(value) = self.cache.get(&key) {
-            self.cache_stats.hit += 1;
-            *value
-        } else {
-            self.cache_stats.miss += 1;
-            let value = self.it(pattern, groups, remaining);
-            self.cache.insert(key, value);
-            value
-        }
+        memo.get_or_insert_with(key, |memo| self.it(memo, pattern, groups, remaining))
     }
 
-    fn it(&mut self, pattern: &[Condition], groups: &[usize], remaining: Remaining) -> usize {
+    fn it(
+        &self,
+        memo: &mut Memo<Key, usize>,
+        pattern: &[Condition],
+        groups: &[usize],
+        remaining: Remaining,
+    ) -> usize {
         use Condition::*;
         use Outcome::*;
 
@@ -161,6 +151,7 @@ impl Cached {
         match outcome {
             Zero => 0,
             UseRemaining => self.rec(
+                memo,
                 next_pattern,
                 groups,
                 match remaining {
@@ -174,6 +165,7 @@ impl Cached {
                         0
                     } else {
                         self.rec(
+                            memo,
                             next_pattern,
                             &groups[1..],
                             Remaining::for_damaged(groups[0] - 1),
@@ -183,7 +175,7 @@ impl Cached {
                     0
                 };
                 let b = if outcome == Skip || outcome == UseNextGroupOrSkip {
-                    self.rec(next_pattern, groups, Remaining::Free)
+                    self.rec(memo, next_pattern, groups, Remaining::Free)
                 } else {
                     0
                 };
@@ -252,7 +244,7 @@ mod tests {
 
         for (line, expected) in test_vectors {
             let record = line.parse().unwrap();
-            let arrangements = count_arrangements(&record);
+            let arrangements = count_arrangements(&mut Memo::new(), &record);
             assert_eq!(arrangements, expected);
         }
     }
@@ -271,7 +263,7 @@ mod tests {
         for (line, expected) in test_vectors {
             let line = expand(line);
             let record = line.parse().unwrap();
-            let arrangements = count_arrangements(&record);
+            let arrangements = count_arrangements(&mut Memo::new(), &record);
             assert_eq!(arrangements, expected);
         }
     }
@@ -1,12 +1,12 @@
 use std::str::FromStr;
 
 use anyhow::Result;
-use indexmap::map::Entry;
-use indexmap::IndexMap;
 use itertools::Itertools;
 
-use crate::challenge::Day;
+use crate::challenge::{Day, ImageExport};
+use crate::utils::cycle;
 use crate::utils::grid::{Coord, Direction, Grid};
+use crate::utils::grid_image::Rgb;
 
 pub fn day() -> Day<usize> {
     Day {
@@ -16,6 +16,11 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        image_exports: vec![ImageExport {
+            label: "cycles",
+            export: cycles_image,
+        }],
+        ..Day::default()
     }
 }
 
@@ -39,30 +44,34 @@ fn total_load(grid: &Grid<char>) -> usize {
 }
 
 fn part2(data: &str) -> Result<usize> {
-    let mut grid: Grid<char> = Grid::from_str(data)?;
-    let mut seen: IndexMap<String, usize> = IndexMap::new();
-    let mut cycle = 0;
-    loop {
-        match seen.entry(grid.to_string()) {
-            Entry::Occupied(entry) => {
-                let period = cycle - entry.get();
-                let remaining_cycles = 1_000_000_000 - cycle;
-                let remaining_cycles = remaining_cycles % period;
-                for _ in 0..remaining_cycles {
-                    grid = slide_cycle(&grid);
-                }
-                break;
-            }
-            Entry::Vacant(entry) => {
-                entry.insert(cycle);
-            }
-        }
-        grid = slide_cycle(&grid);
-        cycle += 1;
-    }
+    let grid: Grid<char> = Grid::from_str(data)?;
+    let grid = cycle::nth_state(grid, 1_000_000_000, slide_cycle, rock_positions);
     Ok(total_load(&grid))
 }
 
+/// The spin cycles, until the platform comes back to an earlier state.
+fn cycles_image(data: &str) -> Result<Vec<Grid<Rgb>>> {
+    let grid: Grid<char> = Grid::from_str(data)?;
+    let (_, states) = cycle::find_repeat(grid, slide_cycle, rock_positions);
+    Ok(states
+        .iter()
+        .map(|grid| {
+            grid.transform(|(_, &cell)| match cell {
+                'O' => [224, 160, 32],
+                '#' => [64, 64, 64],
+                _ => [240, 240, 240],
+            })
+        })
+        .collect())
+}
+
+/// The positions of the rounded rocks, which are all that changes between the states.
+fn rock_positions(grid: &Grid<char>) -> Vec<Coord> {
+    grid.iter()
+        .filter_map(|(coord, cell)| (cell == 'O').then_some(coord))
+        .collect()
+}
+
 fn slide(grid: &Grid<char>, direction: Direction) -> Grid<char> {
     let mut result = grid.transform(|(_, &cell)| if cell == 'O' { '.' } else { cell });
 
@@ -1,10 +1,10 @@
-use std::ops::{AddAssign, Mul};
 use std::str::FromStr;
 
-use anyhow::{bail, Result};
+use anyhow::{Result, bail};
 use itertools::Itertools;
 
 use crate::challenge::Day;
+use crate::utils::geometry::Polygon;
 use crate::utils::grid::Direction;
 
 pub fn day() -> Day<usize> {
@@ -15,6 +15,7 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        ..Day::default()
     }
 }
 
@@ -52,25 +53,13 @@ fn part2(data: &str) -> Result<usize> {
 }
 
 fn solve(steps: Vec<Step>) -> usize {
-    assert_eq!(steps.first().unwrap().dir, Direction::E);
-    assert_eq!(steps.last().unwrap().dir, Direction::N);
-
-    let start = Coord2 { x: 0, y: 0 };
-    let mut coords: Vec<Coord2> = Vec::new();
-    let mut pos = start;
-    for step in steps {
-        coords.push(pos);
-        let v = match step.dir {
-            Direction::N => Coord2 { x: 0, y: 1 },
-            Direction::S => Coord2 { x: 0, y: -1 },
-            Direction::W => Coord2 { x: -1, y: 0 },
-            Direction::E => Coord2 { x: 1, y: 0 },
-        } * isize::try_from(step.len).unwrap();
-        pos += v;
-    }
-    assert_eq!(pos, start);
-
-    trench_area(&coords)
+    let polygon = Polygon::from_steps(
+        steps
+            .into_iter()
+            .map(|step| (step.dir, i64::try_from(step.len).unwrap())),
+    );
+    // the trench is the boundary, plus the lagoon's interior
+    usize::try_from(polygon.lattice_points()).unwrap()
 }
 
 struct DigPlan {
@@ -110,40 +99,3 @@ impl FromStr for Step {
         Ok(Self { dir, len, color })
     }
 }
-
-#[derive(Debug, Hash, Eq, PartialEq, Copy, Clone)]
-struct Coord2 {
-    x: isize,
-    y: isize,
-}
-impl Mul<isize> for Coord2 {
-    type Output = Self;
-    fn mul(self, rhs: isize) -> Self::Output {
-        Self {
-            x: self.x * rhs,
-            y: self.y * rhs,
-        }
-    }
-}
-impl AddAssign for Coord2 {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-    }
-}
-
-fn trench_area(coords: &[Coord2]) -> usize {
-    // Area of a Convex Polygon (Shoelace formula) + add length of outer trench (border)
-    // https://www.mathwords.com/a/area_convex_polygon.htm
-
-    let mut area_double = 0;
-    let mut border = 0;
-    coords
-        .iter()
-        .zip(coords.iter().cycle().skip(1))
-        .for_each(|(c1, c2)| {
-            area_double += c1.x * c2.y - c1.y * c2.x;
-            border += (c2.x - c1.x).abs() + (c2.y - c1.y).abs();
-        });
-    usize::try_from(area_double.abs() + border).unwrap() / 2 + 1
-}
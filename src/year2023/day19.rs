@@ -7,6 +7,8 @@ use itertools::Itertools;
 use strum_macros::{EnumIter, EnumString};
 
 use crate::challenge::Day;
+use crate::utils::graph_export::{GraphExport, Shape};
+use crate::utils::hyperrect::HyperRect;
 
 pub fn day() -> Day<usize> {
     Day {
@@ -16,6 +18,8 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        graph_export: Some(graph),
+        ..Day::default()
     }
 }
 
@@ -48,7 +52,34 @@ fn part2(data: &str) -> Result<usize> {
     let puzzle = data.parse::<Puzzle>()?;
     let solver = Solver::new(puzzle);
     let paths = solver.paths("in");
-    Ok(paths.iter().map(Path::combinations).sum())
+    // every part follows a single path through the workflows, so the paths' hyperrects are disjoint
+    Ok(paths
+        .iter()
+        .filter_map(Path::hyperrect)
+        .map(|rect| rect.volume())
+        .sum())
+}
+
+fn graph(data: &str) -> Result<GraphExport> {
+    let puzzle = data.parse::<Puzzle>()?;
+    let target = |action: &Action| match action {
+        Action::Accept => "A".to_owned(),
+        Action::Reject => "R".to_owned(),
+        Action::GotoWorkflow(name) => name.clone(),
+    };
+    let mut export = GraphExport::new();
+    export
+        .node("A", Shape::DoubleCircle)
+        .node("R", Shape::Circle);
+    for workflow in puzzle.workflows.values() {
+        export.node(&workflow.name, Shape::Box);
+        for rule in &workflow.rules {
+            let label = rule.constraint.to_string();
+            export.edge_with_label(&workflow.name, &target(&rule.action), &label);
+        }
+        export.edge(&workflow.name, &target(&workflow.default_action));
+    }
+    Ok(export)
 }
 
 struct Solver {
@@ -106,35 +137,25 @@ struct Path {
 impl Path {
     const MIN: usize = 1;
     const MAX: usize = 4000;
-    fn combinations(&self) -> usize {
-        let mut possible_values = [[true; Self::MAX]; 4];
-        for constraint in &self.constraints {
-            let range = match constraint.operator {
-                Operator::Lt => constraint.value..=Self::MAX,
-                Operator::Gt => Self::MIN..=constraint.value,
-            };
-            let cat_idx = constraint.category as usize;
-            for i in range {
-                possible_values[cat_idx][i - 1] = false;
-            }
-        }
-        for constraint in &self.negative_constraints {
-            let range = match constraint.operator {
-                Operator::Lt => Self::MIN..constraint.value,
-                #[allow(clippy::range_plus_one)]
-                Operator::Gt => (constraint.value + 1)..Self::MAX + 1,
+    /// Ratings accepted by this path, with one axis per category.
+    #[allow(clippy::range_plus_one)]
+    fn hyperrect(&self) -> Option<HyperRect<usize, 4>> {
+        let all = HyperRect::new([Self::MIN; 4], [Self::MAX + 1; 4]);
+        let allowed = |constraint: &Constraint, satisfied: bool| {
+            let value = constraint.value;
+            let range = match (constraint.operator, satisfied) {
+                (Operator::Lt, true) => Self::MIN..value,
+                (Operator::Lt, false) => value..Self::MAX + 1,
+                (Operator::Gt, true) => value + 1..Self::MAX + 1,
+                (Operator::Gt, false) => Self::MIN..value + 1,
             };
-            let cat_idx = constraint.category as usize;
-            for i in range {
-                possible_values[cat_idx][i - 1] = false;
-            }
-        }
-        let mut product = 1;
-        for possible_values_for_cat in possible_values {
-            let count = possible_values_for_cat.iter().filter(|&&b| b).count();
-            product *= count;
-        }
-        product
+            all.with_axis(constraint.category as usize, range)
+        };
+        self.constraints
+            .iter()
+            .map(|c| allowed(c, true))
+            .chain(self.negative_constraints.iter().map(|c| allowed(c, false)))
+            .try_fold(all, |rect, half_space| rect.intersection(&half_space))
     }
 }
 
@@ -1,15 +1,15 @@
-use std::any::Any;
-use std::collections::VecDeque;
-use std::fmt::{Debug, Display};
-use std::ops::Deref;
 use std::str::FromStr;
 
-use anyhow::{bail, Result};
-use indexmap::map::Entry;
+use anyhow::{Result, anyhow, bail};
 use indexmap::IndexMap;
+use indexmap::map::Entry;
 use itertools::Itertools;
 
 use crate::challenge::Day;
+use crate::utils::graph::{Graph, NodeId};
+use crate::utils::graph_export::{GraphExport, Shape};
+use crate::utils::math::lcm_all;
+use crate::utils::pulse_network::{self, Network, Pulse};
 
 pub fn day() -> Day<u64> {
     Day {
@@ -19,25 +19,19 @@ pub fn day() -> Day<u64> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: true,
+        graph_export: Some(graph),
+        ..Day::default()
     }
 }
 
 fn part1(data: &str) -> Result<u64> {
     let puzzle: Puzzle = data.parse()?;
-    let mut sim = create_simulation(puzzle);
-    let mut low_pulses = 0;
-    let mut high_pulses = 0;
+    let mut network = create_network(&puzzle);
+    let press = button_press(&network)?;
     for _ in 0..1000 {
-        let pulses = push_button(&mut sim);
-        for pulse in pulses {
-            if pulse.high {
-                high_pulses += 1;
-            } else {
-                low_pulses += 1;
-            }
-        }
+        network.run_round(press, |_| {});
     }
-    Ok(low_pulses * high_pulses)
+    Ok(network.pulses(false) * network.pulses(true))
 }
 
 fn part2(data: &str) -> Result<u64> {
@@ -46,162 +40,119 @@ fn part2(data: &str) -> Result<u64> {
     }
 
     let puzzle: Puzzle = data.parse()?;
+    let network = create_network(&puzzle);
+    let press = button_press(&network)?;
+    let graph = network.graph();
+
+    // final architecture: node -[x1]-> input -[x4]-> final_node -> rx
+    // rx receives a low pulse once every input sent a high pulse to final_node during the same
+    // button press, which happens periodically for each of them
+    let rx = graph.id("rx").ok_or_else(|| anyhow!("No rx module"))?;
+    let (&final_node,) = graph
+        .predecessors(rx)
+        .iter()
+        .collect_tuple()
+        .ok_or_else(|| anyhow!("rx should have a single input"))?;
 
-    // final architecture: node -[x1]-> input -[x4]-> final_node(tg) -> rx
-    let (final_node,) = puzzle.inputs["rx"].iter().collect_tuple().unwrap();
-    let nodes = puzzle.inputs[final_node]
+    let periods = graph
+        .predecessors(final_node)
         .iter()
-        .map(|n| {
-            let (input,) = puzzle.inputs[n].iter().collect_tuple().unwrap();
-            input
+        .map(|&input| {
+            let mut network = network.clone();
+            let mut high_pulse = || {
+                network
+                    .run_until(press, 10_000, |pulse| {
+                        pulse.source == input && pulse.destination == final_node && pulse.high
+                    })
+                    .ok_or_else(|| anyhow!("No high pulse from {}", graph.name(input)))
+            };
+            // the first high pulse only gives the period if the next one comes as much later
+            let period = high_pulse()?;
+            let next = high_pulse()?;
+            if next != 2 * period {
+                bail!(
+                    "High pulses from {} at rounds {period} and {next} are not periodic",
+                    graph.name(input)
+                );
+            }
+            Ok(period)
         })
-        .cloned()
-        .collect_vec();
+        .collect::<Result<Vec<_>>>()?;
 
-    let mut sim = create_simulation(puzzle);
-
-    let capacity = 10_000;
-
-    let mut detectors: IndexMap<String, PeriodDetector<String>> = IndexMap::new();
-
-    for _ in 0..capacity {
-        push_button(&mut sim);
-
-        for node in nodes.clone() {
-            let module = sim.modules.get_mut(&node).unwrap();
-            let state = module.state();
-            let state: &IndexMap<String, bool> = state.deref().downcast_ref().unwrap();
-            let state_str = format!("{:?}", state);
-            detectors
-                .entry(node.clone())
-                .or_insert_with(|| PeriodDetector::with_capacity(capacity))
-                .push(state_str);
-        }
-    }
-
-    let product = detectors
-        .values()
-        .map(|detector| detector.period().unwrap())
-        .map(|period| period as u64)
-        .product::<u64>();
-
-    Ok(product)
-}
-
-struct PeriodDetector<T> {
-    history: Vec<T>,
+    lcm_all(periods).ok_or_else(|| anyhow!("The least common multiple overflows"))
 }
-impl<T> PeriodDetector<T>
-where
-    T: PartialEq,
-{
-    fn with_capacity(capacity: usize) -> Self {
-        Self {
-            history: Vec::with_capacity(capacity),
-        }
-    }
-    fn push(&mut self, value: T) {
-        self.history.push(value);
-    }
 
-    fn period(&self) -> Option<usize> {
-        'outer: for period in 1..=self.history.len() / 2 {
-            for i in 0..period {
-                for r in 1.. {
-                    let j = i + r * period;
-                    if j >= self.history.len() {
-                        break;
-                    }
-                    if self.history[i] != self.history[j] {
-                        continue 'outer;
-                    }
-                }
-            }
-            return Some(period);
+fn graph(data: &str) -> Result<GraphExport> {
+    let puzzle: Puzzle = data.parse()?;
+    let mut export = GraphExport::new();
+    export
+        .node("button", Shape::Circle)
+        .edge("button", "broadcaster");
+    for module in &puzzle.modules {
+        let shape = match module.r#type {
+            ModuleType::Broadcast => Shape::Hexagon,
+            ModuleType::FlipFlop => Shape::Box,
+            ModuleType::Conjunction => Shape::Diamond,
+        };
+        export.node(&module.name, shape);
+        for destination in &module.destinations {
+            export.edge(&module.name, destination);
         }
-        None
     }
+    Ok(export)
 }
 
-struct Simulation {
-    modules: IndexMap<String, Box<dyn Module>>,
-}
-
-fn push_button(simulation: &mut Simulation) -> Vec<Pulse> {
-    let mut pulse_queue: VecDeque<Pulse> = VecDeque::new();
-    pulse_queue.push_back(Pulse {
-        destination: "broadcaster".to_owned(),
+fn button_press(network: &Network<Module>) -> Result<Pulse> {
+    let graph = network.graph();
+    Ok(Pulse {
+        source: graph.id("button").unwrap(),
+        destination: graph
+            .id("broadcaster")
+            .ok_or_else(|| anyhow!("No broadcaster module"))?,
         high: false,
-        source: "button".to_owned(),
-    });
-
-    let mut processed_pulses: Vec<Pulse> = vec![];
-
-    loop {
-        let pulse = pulse_queue.pop_front();
-        if pulse.is_none() {
-            break;
-        }
-        let pulse = pulse.unwrap();
-        processed_pulses.push(pulse.clone());
-
-        let module = simulation.modules.get_mut(&pulse.destination);
-        if module.is_none() {
-            // ignore unknown modules: they do not have any destinations
-            continue;
-        }
-        let module = module.unwrap();
-
-        let new_pulses = module.process(pulse);
+    })
+}
 
-        for pulse in new_pulses {
-            pulse_queue.push_back(pulse);
+fn create_network(puzzle: &Puzzle) -> Network<Module> {
+    let mut graph = Graph::new();
+    graph.add_node("button");
+    for module in &puzzle.modules {
+        graph.add_node(&module.name);
+        for destination in &module.destinations {
+            graph.add_edge(&module.name, destination);
         }
     }
-
-    processed_pulses
-}
-
-fn create_simulation(puzzle: Puzzle) -> Simulation {
-    let modules = puzzle
+    let types: IndexMap<NodeId, &ModuleType> = puzzle
         .modules
-        .into_iter()
-        .map(|cfg| {
-            let name = cfg.name.clone();
-            let enriched_cfg = EnrichedModuleConfig {
-                module_config: cfg,
-                inputs: puzzle.inputs.get(&name).unwrap_or(&vec![]).clone(),
-            };
-            let module: Box<dyn Module> = enriched_cfg.into();
-            (name, module)
-        })
+        .iter()
+        .map(|module| (graph.id(&module.name).unwrap(), &module.r#type))
         .collect();
-    Simulation { modules }
+
+    Network::new(graph, |graph, id| {
+        types.get(&id).map(|r#type| match r#type {
+            ModuleType::Broadcast => Module::Broadcast,
+            ModuleType::FlipFlop => Module::FlipFlop { on: false },
+            ModuleType::Conjunction => Module::Conjunction {
+                last_received_from_was_high: graph
+                    .predecessors(id)
+                    .iter()
+                    .map(|&input| (input, false))
+                    .collect(),
+            },
+        })
+    })
 }
 
 #[derive(Debug)]
 struct Puzzle {
     modules: Vec<ModuleConfig>,
-    inputs: IndexMap<String, Vec<String>>,
 }
 impl FromStr for Puzzle {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         let modules: Vec<ModuleConfig> = s.lines().map(str::parse).collect::<Result<Vec<_>>>()?;
-
-        let mut inputs: IndexMap<String, Vec<String>> = IndexMap::new();
-
-        for module in &modules {
-            for destination in &module.destinations {
-                inputs
-                    .entry(destination.to_owned())
-                    .or_default()
-                    .push(module.name.clone());
-            }
-        }
-
-        Ok(Self { modules, inputs })
+        Ok(Self { modules })
     }
 }
 #[derive(Debug)]
@@ -243,129 +194,43 @@ impl FromStr for ModuleConfig {
     }
 }
 
-struct EnrichedModuleConfig {
-    module_config: ModuleConfig,
-    inputs: Vec<String>,
-}
-
-impl From<EnrichedModuleConfig> for Box<dyn Module> {
-    fn from(enriched_cfg: EnrichedModuleConfig) -> Box<dyn Module> {
-        let cfg = enriched_cfg.module_config;
-        let base_module = BaseModule {
-            destinations: cfg.destinations,
-        };
-        match cfg.r#type {
-            ModuleType::Broadcast => Box::new(BroadcastModule { base_module }),
-            ModuleType::FlipFlop => Box::new(FlipFlopModule {
-                base_module,
-                on: false,
-            }),
-            ModuleType::Conjunction => Box::new(ConjunctionModule {
-                base_module,
-                last_received_from_was_high: enriched_cfg
-                    .inputs
-                    .into_iter()
-                    .map(|name| (name, false))
-                    .collect(),
-            }),
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
-struct Pulse {
-    destination: String,
-    high: bool,
-    source: String,
-}
-impl Display for Pulse {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{} -{}-> {}",
-            self.source,
-            if self.high { "high" } else { "low" },
-            self.destination
-        )
-    }
-}
-
-trait Module: Debug {
-    fn process(&mut self, pulse: Pulse) -> Vec<Pulse>;
-    #[allow(dead_code)]
-    fn done(&self) -> bool {
-        false
-    }
-    fn state(&self) -> Box<dyn Any> {
-        Box::new(())
-    }
-}
-
-#[derive(Debug)]
-struct BaseModule {
-    destinations: Vec<String>,
-}
-impl BaseModule {
-    fn new_pulses_from(&self, high: bool, source: &str) -> Vec<Pulse> {
-        self.destinations
-            .iter()
-            .map(|name| Pulse {
-                destination: name.to_owned(),
-                high,
-                source: source.to_owned(),
-            })
-            .collect_vec()
-    }
-}
-
-#[derive(Debug)]
-struct BroadcastModule {
-    base_module: BaseModule,
-}
-impl Module for BroadcastModule {
-    fn process(&mut self, pulse: Pulse) -> Vec<Pulse> {
-        self.base_module
-            .new_pulses_from(pulse.high, &pulse.destination)
-    }
-}
-
-#[derive(Debug)]
-struct FlipFlopModule {
-    base_module: BaseModule,
-    on: bool,
-}
-impl Module for FlipFlopModule {
-    fn process(&mut self, pulse: Pulse) -> Vec<Pulse> {
-        if pulse.high {
-            vec![]
-        } else {
-            self.on = !self.on;
-            self.base_module
-                .new_pulses_from(self.on, &pulse.destination)
-        }
-    }
-}
-
-#[derive(Debug)]
-struct ConjunctionModule {
-    base_module: BaseModule,
-    last_received_from_was_high: IndexMap<String, bool>,
+enum Module {
+    Broadcast,
+    FlipFlop {
+        on: bool,
+    },
+    Conjunction {
+        last_received_from_was_high: IndexMap<NodeId, bool>,
+    },
 }
-impl Module for ConjunctionModule {
-    fn process(&mut self, pulse: Pulse) -> Vec<Pulse> {
-        match self.last_received_from_was_high.entry(pulse.source.clone()) {
-            Entry::Occupied(mut entry) => {
-                entry.insert(pulse.high);
+impl pulse_network::Module for Module {
+    fn receive(&mut self, pulse: &Pulse) -> Option<bool> {
+        match self {
+            Module::Broadcast => Some(pulse.high),
+            Module::FlipFlop { on } => {
+                if pulse.high {
+                    None
+                } else {
+                    *on = !*on;
+                    Some(*on)
+                }
             }
-            Entry::Vacant(_) => {
-                panic!("received pulse from unknown source: {:?}", pulse);
+            Module::Conjunction {
+                last_received_from_was_high,
+            } => {
+                match last_received_from_was_high.entry(pulse.source) {
+                    Entry::Occupied(mut entry) => {
+                        entry.insert(pulse.high);
+                    }
+                    Entry::Vacant(_) => {
+                        panic!("received pulse from unknown source: {:?}", pulse);
+                    }
+                }
+                let low = last_received_from_was_high.values().all(|&high| high);
+                Some(!low)
             }
         }
-        let low = self.last_received_from_was_high.values().all(|&high| high);
-        self.base_module.new_pulses_from(!low, &pulse.destination)
-    }
-    fn state(&self) -> Box<dyn Any> {
-        Box::new(self.last_received_from_was_high.clone())
     }
 }
 
@@ -375,9 +240,20 @@ mod tests {
 
     use super::*;
 
-    fn push_button_and_check(simulation: &mut Simulation, expected: &str) {
-        let pulses = push_button(simulation);
-        let actual = pulses.iter().map(ToString::to_string).join("\n");
+    fn push_button_and_check(network: &mut Network<Module>, expected: &str) {
+        let press = button_press(network).unwrap();
+        let mut pulses = vec![];
+        network.run_round(press, |pulse| pulses.push(*pulse));
+        let graph = network.graph();
+        let actual = pulses
+            .iter()
+            .map(|pulse| {
+                let level = if pulse.high { "high" } else { "low" };
+                let (source, destination) =
+                    (graph.name(pulse.source), graph.name(pulse.destination));
+                format!("{source} -{level}-> {destination}")
+            })
+            .join("\n");
         assert_eq!(actual, trim_lines(expected));
     }
 
@@ -385,7 +261,7 @@ mod tests {
     fn test_example1_step() {
         let data = day().read_data_file("example1").unwrap();
         let puzzle: Puzzle = data.parse().unwrap();
-        let mut sim = create_simulation(puzzle);
+        let mut network = create_network(&puzzle);
         let expected = r"
             button -low-> broadcaster
             broadcaster -low-> a
@@ -400,7 +276,7 @@ mod tests {
             c -low-> inv
             inv -high-> a        
         ";
-        push_button_and_check(&mut sim, expected);
+        push_button_and_check(&mut network, expected);
     }
 
     #[test]
@@ -414,7 +290,7 @@ mod tests {
         ";
         let data = trim_lines(data);
         let puzzle: Puzzle = data.parse().unwrap();
-        let mut sim = create_simulation(puzzle);
+        let mut network = create_network(&puzzle);
 
         // push button
         let expected = r"
@@ -427,7 +303,7 @@ mod tests {
             b -high-> con
             con -low-> output
             ";
-        push_button_and_check(&mut sim, expected);
+        push_button_and_check(&mut network, expected);
 
         // push button a second time
         let expected = r"
@@ -438,7 +314,7 @@ mod tests {
             inv -high-> b
             con -high-> output
         ";
-        push_button_and_check(&mut sim, expected);
+        push_button_and_check(&mut network, expected);
 
         // push button a third time
         let expected = r"
@@ -451,7 +327,7 @@ mod tests {
             b -low-> con
             con -high-> output
         ";
-        push_button_and_check(&mut sim, expected);
+        push_button_and_check(&mut network, expected);
 
         // push button a fourth time
         let expected = r"
@@ -462,7 +338,7 @@ mod tests {
             inv -high-> b
             con -high-> output
         ";
-        push_button_and_check(&mut sim, expected);
+        push_button_and_check(&mut network, expected);
     }
 
     #[test]
@@ -477,13 +353,4 @@ mod tests {
         let data = trim_lines(data);
         assert_eq!(part1(&data).unwrap(), 11687500);
     }
-
-    #[test]
-    fn period_detector_works() {
-        let mut detector = PeriodDetector::with_capacity(1);
-        for b in [0, 0, 1, 1, 1, 0, 0, 0, 1, 1, 1, 0] {
-            detector.push(b);
-        }
-        assert_eq!(detector.period(), Some(6));
-    }
 }
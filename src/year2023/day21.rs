@@ -1,12 +1,13 @@
+use std::collections::HashSet;
+
 use anyhow::Result;
 use itertools::Itertools;
 use pathfinding::prelude::bfs_reach;
-use polyfit_rs::polyfit_rs::polyfit;
 use strum::IntoEnumIterator;
 
-use crate::challenge::Day;
-use crate::utils::f64_conversions::{try_f64_from_usize, try_usize_from_f64};
+use crate::challenge::{Day, Part, Variant};
 use crate::utils::grid::{Coord, Direction, Grid};
+use crate::utils::poly::NewtonPoly;
 
 pub fn day() -> Day<usize> {
     Day {
@@ -16,6 +17,12 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        variants: vec![Variant {
+            part: Part::Part1,
+            name: "infinite",
+            solver: part1_infinite,
+        }],
+        ..Day::default()
     }
 }
 
@@ -28,6 +35,15 @@ fn part1(data: &str) -> Result<usize> {
     Ok(ends.len())
 }
 
+/// Same as part 1, walking on the infinitely repeated garden (which 64 steps do not leave).
+fn part1_infinite(data: &str) -> Result<usize> {
+    if data.is_empty() {
+        return Ok(0);
+    }
+    let grid: Grid<char> = data.parse()?;
+    Ok(part2_reach(&grid, &[64])[0])
+}
+
 fn part2(data: &str) -> Result<usize> {
     const STEPS: usize = 26501365;
 
@@ -36,29 +52,17 @@ fn part2(data: &str) -> Result<usize> {
     }
     let grid: Grid<char> = data.parse()?;
 
-    let (xs, ys): (Vec<f64>, Vec<f64>) = (0..3)
-        .map(|i| {
-            let steps = STEPS % grid.w + i * grid.w;
-            (
-                try_f64_from_usize(i).unwrap(),
-                try_f64_from_usize(part2_reach(&grid, steps)).unwrap(),
-            )
-        })
-        .unzip();
-
-    let coeffs = polyfit(&xs, &ys, 2).unwrap();
-
-    let (coeff_c, coeff_b, coeff_a) = coeffs
-        .iter()
-        .map(|f| f.round())
-        .map(|f| try_usize_from_f64(f).unwrap())
-        .collect_tuple()
-        .unwrap();
+    // reachable plots grow quadratically with the number of grid widths walked, which the 4th
+    // sample checks
+    let steps = (0..4).map(|i| STEPS % grid.w + i * grid.w).collect_vec();
+    let ys = part2_reach(&grid, &steps)
+        .into_iter()
+        .map(i128::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    let poly = NewtonPoly::fit_with_degree(&ys, 2)?;
 
     let x = STEPS / grid.w; // ignore remainder
-    let y = (coeff_a * x * x) + (coeff_b * x) + coeff_c;
-
-    Ok(y)
+    Ok(usize::try_from(poly.eval(i128::try_from(x)?))?)
 }
 
 fn part1_reach(grid: &Grid<char>, steps: usize) -> Vec<Coord> {
@@ -97,49 +101,37 @@ fn part1_reach(grid: &Grid<char>, steps: usize) -> Vec<Coord> {
     .collect()
 }
 
-fn part2_reach(grid: &Grid<char>, steps: usize) -> usize {
-    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-    struct Pos {
-        coord: (isize, isize),
-        steps: usize,
-    }
-
-    let successors = |p: &Pos| {
-        let (x, y) = p.coord;
-        Direction::iter()
-            .map(|dir| match dir {
-                Direction::N => (x, y - 1),
-                Direction::S => (x, y + 1),
-                Direction::E => (x + 1, y),
-                Direction::W => (x - 1, y),
-            })
-            .filter(|(x, y)| grid.get(&grid.map_virtual(*x, *y)) != &'#')
-            .map(|c| Pos {
-                coord: c,
-                steps: p.steps + 1,
-            })
-            .collect_vec()
-    };
-
+/// Number of plots reachable in exactly each of the given numbers of steps, walking on the
+/// infinitely repeated garden.
+fn part2_reach(grid: &Grid<char>, steps: &[usize]) -> Vec<usize> {
     let (start,) = grid
         .iter()
         .filter_map(|(coord, c)| (c == 'S').then_some(coord))
         .collect_tuple()
         .unwrap();
-
     let Coord(x, y) = start;
-    let (x, y) = (isize::try_from(x).unwrap(), isize::try_from(y).unwrap());
+    let start = (isize::try_from(x).unwrap(), isize::try_from(y).unwrap());
+
+    // plots first reached after each number of steps
+    let max_steps = steps.iter().copied().max().unwrap_or_default();
+    let mut layers = vec![];
+    let mut seen = HashSet::from([start]);
+    let mut frontier = vec![start];
+    for _ in 0..=max_steps {
+        layers.push(frontier.len());
+        frontier = frontier
+            .into_iter()
+            .flat_map(|(x, y)| [(x, y - 1), (x, y + 1), (x + 1, y), (x - 1, y)])
+            .filter(|&(x, y)| grid.get(&grid.map_virtual(x, y)) != &'#')
+            .filter(|&p| seen.insert(p))
+            .collect();
+    }
 
-    bfs_reach(
-        Pos {
-            coord: (x, y),
-            steps: 0,
-        },
-        successors,
-    )
-    .take_while(|pos| pos.steps <= steps)
-    .filter_map(|pos| (pos.steps == steps).then_some(pos.coord))
-    .count()
+    // a plot reached in some steps is reached again 2 steps later, going back and forth
+    steps
+        .iter()
+        .map(|&n| layers[..=n].iter().skip(n % 2).step_by(2).sum())
+        .collect()
 }
 
 #[cfg(test)]
@@ -1,30 +1,45 @@
-use std::fmt::{Display, Formatter};
-use std::ops::RangeInclusive;
+use std::ops::Range;
 use std::str::FromStr;
 
-use anyhow::{Result, bail};
+use anyhow::{Result, anyhow, bail};
 use indexmap::{IndexMap, IndexSet};
-use itertools::Itertools;
+use itertools::{Itertools, iproduct};
 
-use crate::challenge::Day;
+use crate::challenge::{Day, Parsed};
+use crate::utils::grid3::{Grid3, Voxel};
+use crate::utils::hyperrect::HyperRect;
+use crate::utils::point3::{Aabb, Point3};
 
 pub fn day() -> Day<usize> {
-    Day {
-        part1_solutions: (5, Some(477)),
-        part2_solutions: Some((7, Some(61555))),
-        part1_solver: part1,
-        part2_solver: part2,
-        source_file: file!(),
-        distinct_examples: false,
+    Day::parsed::<Puzzle>((5, Some(477)), Some((7, Some(61555))), file!())
+}
+
+/// Both parts work on the settled bricks.
+struct Puzzle;
+
+impl Parsed for Puzzle {
+    type Input = CommonPart;
+    type Answer = usize;
+
+    fn parse(data: &str) -> Result<CommonPart> {
+        common_part(data)
+    }
+
+    fn part1(input: &CommonPart) -> Result<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &CommonPart) -> Result<usize> {
+        part2(input)
     }
 }
 
-fn part1(data: &str) -> Result<usize> {
+fn part1(input: &CommonPart) -> Result<usize> {
     let CommonPart {
         grid,
         supporters,
         supporting,
-    } = common_part(data)?;
+    } = input;
 
     let mut removable = 0;
 
@@ -45,7 +60,7 @@ fn part1(data: &str) -> Result<usize> {
     Ok(removable)
 }
 
-fn part2(data: &str) -> Result<usize> {
+fn part2(input: &CommonPart) -> Result<usize> {
     #[derive(Clone)]
     struct State {
         supporters: IndexMap<BrickId, IndexSet<BrickId>>,
@@ -56,11 +71,11 @@ fn part2(data: &str) -> Result<usize> {
         grid,
         supporters,
         supporting,
-    } = common_part(data)?;
+    } = input;
 
-    let to_set_values = |m: IndexMap<BrickId, Vec<BrickId>>| {
-        m.into_iter()
-            .map(|(k, v)| (k, IndexSet::from_iter(v)))
+    let to_set_values = |m: &IndexMap<BrickId, Vec<BrickId>>| {
+        m.iter()
+            .map(|(k, v)| (k.clone(), v.iter().cloned().collect()))
             .collect()
     };
 
@@ -125,13 +140,10 @@ fn common_part(data: &str) -> Result<CommonPart> {
                 break;
             }
 
-            let xys_at_z = brick.xys_at_z(z);
-
-            let can_move = if let Some(x) = grid.z.get(&(z - 1)) {
-                !xys_at_z.iter().any(|xy| x.contains_key(xy))
-            } else {
-                true
-            };
+            let can_move = brick
+                .voxels_at_z(z)
+                .iter()
+                .all(|voxel| grid.brick_at(&(*voxel - Point3::new(0, 0, 1))).is_none());
 
             if !can_move {
                 break;
@@ -170,69 +182,31 @@ fn common_part(data: &str) -> Result<CommonPart> {
 }
 
 #[derive(Debug, Clone)]
-enum Brick {
-    Cube {
-        x: usize,
-        y: usize,
-        z: usize,
-    },
-    XLine {
-        x: RangeInclusive<usize>,
-        y: usize,
-        z: usize,
-    },
-    YLine {
-        x: usize,
-        y: RangeInclusive<usize>,
-        z: usize,
-    },
-    ZLine {
-        x: usize,
-        y: usize,
-        z: RangeInclusive<usize>,
-    },
-}
+struct Brick(HyperRect<usize, 3>);
 
 impl Brick {
     fn min_z(&self) -> usize {
-        use Brick::*;
-        match self {
-            Cube { z, .. } | XLine { z, .. } | YLine { z, .. } => *z,
-            ZLine { z, .. } => *z.start(),
-        }
+        self.0.min[2]
     }
-    fn coords(&self) -> Vec<Coord3D> {
-        use Brick::*;
-        match self {
-            Cube { x, y, z } => vec![(*x, *y, *z)],
-            XLine { x, y, z } => x.clone().map(|x| (x, *y, *z)).collect(),
-            YLine { x, y, z } => y.clone().map(|y| (*x, y, *z)).collect(),
-            ZLine { x, y, z } => z.clone().map(|z| (*x, *y, z)).collect(),
-        }
+    fn voxels(&self) -> Vec<Voxel> {
+        self.voxels_in(self.0.ranges()[2].clone())
     }
-    fn xys_at_z(&self, at_z: usize) -> Vec<(usize, usize)> {
-        use Brick::*;
-        match self {
-            Cube { x, y, z } if *z == at_z => vec![(*x, *y)],
-            XLine { x, y, z } if *z == at_z => x.clone().map(|x| (x, *y)).collect(),
-            YLine { x, y, z } if *z == at_z => y.clone().map(|y| (*x, y)).collect(),
-            ZLine { x, y, z } if z.contains(&at_z) => vec![(*x, *y)],
-            _ => vec![],
+    fn voxels_at_z(&self, z: usize) -> Vec<Voxel> {
+        if self.0.ranges()[2].contains(&z) {
+            self.voxels_in(z..z + 1)
+        } else {
+            vec![]
         }
     }
+    fn voxels_in(&self, z: Range<usize>) -> Vec<Voxel> {
+        let [x, y, _] = self.0.ranges();
+        iproduct!(z, y, x)
+            .map(|(z, y, x)| Point3::from([x, y, z].map(|c| i64::try_from(c).unwrap())))
+            .collect()
+    }
     pub fn move_down(self) -> Self {
-        use Brick::*;
-        match self {
-            Cube { x, y, z } => Cube { x, y, z: z - 1 },
-            XLine { x, y, z } => XLine { x, y, z: z - 1 },
-            YLine { x, y, z } => YLine { x, y, z: z - 1 },
-            ZLine { x, y, z } => ZLine {
-                x,
-                y,
-                #[allow(clippy::range_minus_one)]
-                z: (z.start() - 1)..=(z.end() - 1),
-            },
-        }
+        let z = self.0.min[2] - 1..self.0.max[2] - 1;
+        Self(self.0.with_axis(2, z))
     }
 }
 
@@ -240,80 +214,53 @@ impl FromStr for Brick {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use Brick::*;
         let (start, end) = s.split_once('~').unwrap();
-        let parse_xyz = |s: &str| {
+        let parse_xyz = |s: &str| -> Result<[usize; 3]> {
             let (x, y, z) = s
                 .split(',')
-                .map(|s| s.parse::<usize>().unwrap())
+                .map(str::parse::<usize>)
                 .collect_tuple()
-                .unwrap();
-            (x, y, z)
+                .ok_or_else(|| anyhow!("Invalid coordinates: {}", s))?;
+            Ok([x?, y?, z?])
         };
-        let (start_x, start_y, start_z) = parse_xyz(start);
-        let (end_x, end_y, end_z) = parse_xyz(end);
-        let (len_x, len_y, len_z) = (end_x - start_x, end_y - start_y, end_z - start_z);
-        let brick = match (len_x, len_y, len_z) {
-            (0, 0, 0) => Cube {
-                x: start_x,
-                y: start_y,
-                z: start_z,
-            },
-            (x, 0, 0) if x > 0 => XLine {
-                x: start_x..=end_x,
-                y: start_y,
-                z: start_z,
-            },
-            (0, y, 0) if y > 0 => YLine {
-                x: start_x,
-                y: start_y..=end_y,
-                z: start_z,
-            },
-            (0, 0, z) if z > 0 => ZLine {
-                x: start_x,
-                y: start_y,
-                z: start_z..=end_z,
-            },
-            _ => bail!("Invalid brick: {}", s),
-        };
-        Ok(brick)
+        let (start, end) = (parse_xyz(start)?, parse_xyz(end)?);
+        if start.iter().zip(&end).any(|(start, end)| start > end) {
+            bail!("Invalid brick: {}", s);
+        }
+        Ok(Self(HyperRect::new(start, end.map(|c| c + 1))))
     }
 }
 
-type Coord3D = (usize, usize, usize);
 type BrickId = String;
 
 struct BrickGrid {
     bricks: IndexMap<BrickId, Brick>,
-    // z -> (x,y) -> brick_id
-    z: IndexMap<usize, IndexMap<(usize, usize), BrickId>>,
+    cells: Grid3<Option<BrickId>>,
 }
 
 impl BrickGrid {
-    fn new() -> Self {
+    fn new(bounds: Aabb<i64>) -> Self {
         Self {
             bricks: IndexMap::new(),
-            z: IndexMap::new(),
+            cells: Grid3::new(bounds, None),
         }
     }
 
-    fn brick_at(&self, coord: Coord3D) -> Option<&BrickId> {
-        let (x, y, z) = coord;
-        self.z.get(&z).and_then(|z| z.get(&(x, y)))
+    fn brick_at(&self, voxel: &Voxel) -> Option<&BrickId> {
+        self.cells.get(voxel).and_then(Option::as_ref)
     }
 
     fn add_brick(&mut self, id: BrickId, brick: Brick) -> bool {
         if self.bricks.contains_key(&id) {
             return false;
         }
-        for coord in brick.coords() {
-            if self.brick_at(coord).is_some() {
+        for voxel in brick.voxels() {
+            if self.brick_at(&voxel).is_some() {
                 return false;
             }
         }
-        for coord in brick.coords() {
-            let (x, y, z) = coord;
-            self.z.entry(z).or_default().insert((x, y), id.clone());
+        for voxel in brick.voxels() {
+            self.cells.set(&voxel, Some(id.clone()));
         }
         self.bricks.insert(id, brick);
         true
@@ -321,71 +268,35 @@ impl BrickGrid {
 
     pub fn remove_brick(&mut self, id: &BrickId) {
         let brick = self.bricks.swap_remove(id).unwrap();
-        for coord in brick.coords() {
-            let (x, y, z) = coord;
-            let xy = self.z.get_mut(&z).unwrap();
-            let removed = xy.swap_remove(&(x, y)).unwrap();
+        for voxel in brick.voxels() {
+            let removed = self.cells.get_mut(&voxel).unwrap().take().unwrap();
             assert_eq!(&removed, id);
-            if xy.is_empty() {
-                self.z.swap_remove(&z);
-            }
         }
     }
 
     pub fn replace_brick(&mut self, id: &BrickId, brick: Brick) {
         self.remove_brick(id);
-        self.add_brick(id.clone(), brick);
+        let added = self.add_brick(id.clone(), brick);
+        assert!(added, "Brick {id} overlaps another brick");
     }
 
-    #[allow(dead_code)]
-    pub fn x_view(&self) -> LateralView<'_> {
-        LateralView {
-            grid: self,
-            x_side: true,
-        }
-    }
-    #[allow(dead_code)]
-    pub fn y_view(&self) -> LateralView<'_> {
-        LateralView {
-            grid: self,
-            x_side: false,
-        }
+    #[cfg(test)]
+    fn x_view(&self) -> String {
+        self.cells.projection(1, |id| id.chars().next().unwrap())
     }
 
-    pub fn x_range(&self) -> RangeInclusive<usize> {
-        let (&min, &max) = self
-            .z
-            .values()
-            .flat_map(|xy| xy.keys())
-            .map(|(x, _)| x)
-            .minmax()
-            .into_option()
-            .unwrap();
-        min..=max
-    }
-    pub fn y_range(&self) -> RangeInclusive<usize> {
-        let (&min, &max) = self
-            .z
-            .values()
-            .flat_map(|xy| xy.keys())
-            .map(|(_, y)| y)
-            .minmax()
-            .into_option()
-            .unwrap();
-        min..=max
-    }
-
-    pub fn z_range(&self) -> RangeInclusive<usize> {
-        let (&min, &max) = self.z.keys().minmax().into_option().unwrap();
-        min..=max
+    #[cfg(test)]
+    fn y_view(&self) -> String {
+        self.cells.projection(0, |id| id.chars().next().unwrap())
     }
 
     pub fn supporters(&self, id: &BrickId) -> IndexSet<&BrickId> {
         let brick = self.bricks.get(id).unwrap();
         let brick_min_z = brick.min_z();
         let mut supporting = IndexSet::new();
-        for (x, y) in brick.xys_at_z(brick_min_z) {
-            if let Some(id_below) = self.brick_at((x, y, brick_min_z - 1)) {
+        for voxel in brick.voxels_at_z(brick_min_z) {
+            let below = voxel - Point3::new(0, 0, 1);
+            if let Some(id_below) = self.brick_at(&below) {
                 supporting.insert(id_below);
             }
         }
@@ -393,66 +304,78 @@ impl BrickGrid {
     }
 }
 
-struct LateralView<'a> {
-    grid: &'a BrickGrid,
-    x_side: bool,
-}
-
-impl Display for LateralView<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        fn x_coord((a, other): (usize, usize), z: usize) -> (usize, usize, usize) {
-            (a, other, z)
-        }
-        fn y_coord((a, other): (usize, usize), z: usize) -> (usize, usize, usize) {
-            (other, a, z)
-        }
-
-        let grid = self.grid;
-        let x_range = grid.x_range();
-        let y_range = grid.y_range();
-        let z_range = grid.z_range();
-
-        let (range, other_range) = if self.x_side {
-            (&x_range, &y_range)
-        } else {
-            (&y_range, &x_range)
-        };
-        for z in z_range.rev() {
-            for a in range.clone() {
-                let ids: IndexSet<_> = other_range
-                    .clone()
-                    .filter_map(|other| {
-                        grid.brick_at(if self.x_side {
-                            x_coord((a, other), z)
-                        } else {
-                            y_coord((a, other), z)
-                        })
-                    })
-                    .collect();
-                let c = match ids.iter().collect_vec().as_slice() {
-                    [] => '.',
-                    [id] => id.chars().next().unwrap(),
-                    _ => '?',
-                };
-                write!(f, "{}", c)?;
-            }
-            writeln!(f, " {}", z)?;
-        }
-        Ok(())
-    }
-}
-
 impl From<Vec<Brick>> for BrickGrid {
     fn from(bricks: Vec<Brick>) -> Self {
-        let mut grid = BrickGrid::new();
+        let mut bounds = Aabb::from_points(bricks.iter().flat_map(Brick::voxels)).unwrap_or(Aabb {
+            min: Point3::new(0, 0, 1),
+            max: Point3::new(0, 0, 1),
+        });
+        // down to the ground, which the bricks fall to
+        bounds.min.z = 1;
+        let mut grid = BrickGrid::new(bounds);
         for (i, brick) in bricks.iter().enumerate() {
             let brick_id = if bricks.len() <= 26 {
                 ((b'A' + u8::try_from(i).unwrap()) as char).to_string()
             } else {
                 i.to_string()
             };
-            grid.add_brick(brick_id, brick.clone());
+            let added = grid.add_brick(brick_id.clone(), brick.clone());
+            assert!(added, "Brick {brick_id} overlaps another brick");
         }
         grid
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn bricks_fall_to_the_ground() {
+        let input = common_part("0,0,3~2,0,3\n1,0,5~1,2,5\n1,1,9~1,1,10").unwrap();
+        let min_z = ["A", "B", "C"].map(|id| input.grid.bricks[id].min_z());
+        assert_eq!(min_z, [1, 2, 3]);
+        assert_eq!(part1(&input).unwrap(), 1);
+        assert_eq!(part2(&input).unwrap(), 3);
+    }
+
+    #[test]
+    fn views_show_the_settled_bricks() {
+        let input = common_part(indoc! {"
+            1,0,1~1,2,1
+            0,0,2~2,0,2
+            0,2,3~2,2,3
+            0,0,4~0,2,4
+            2,0,5~2,2,5
+            0,1,6~2,1,6
+            1,1,8~1,1,9
+        "})
+        .unwrap();
+        let x_view = indoc! {"
+            ... 9
+            ... 8
+            ... 7
+            .G. 6
+            .G. 5
+            FFF 4
+            D.E 3
+            ??? 2
+            .A. 1
+        "};
+        let y_view = indoc! {"
+            ... 9
+            ... 8
+            ... 7
+            .G. 6
+            .G. 5
+            .F. 4
+            ??? 3
+            B.C 2
+            AAA 1
+        "};
+        assert_eq!(input.grid.x_view(), x_view);
+        assert_eq!(input.grid.y_view(), y_view);
+    }
+}
@@ -15,6 +15,7 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: true,
+        ..Day::default()
     }
 }
 
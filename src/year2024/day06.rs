@@ -1,5 +1,7 @@
-use crate::challenge::Day;
+use crate::challenge::{Day, ImageExport};
+use crate::utils::cycle;
 use crate::utils::grid::{Coord, Direction, Grid, Turn};
+use crate::utils::grid_image::Rgb;
 use anyhow::Result;
 use indexmap::IndexSet;
 use strum_macros::{EnumIter, EnumString};
@@ -12,6 +14,11 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        image_exports: vec![ImageExport {
+            label: "path",
+            export: path_image,
+        }],
+        ..Day::default()
     }
 }
 
@@ -73,6 +80,16 @@ fn part1_grid(data: &str) -> Result<Grid<Cell>> {
     Ok(grid)
 }
 
+/// The cells visited by the guard.
+fn path_image(data: &str) -> Result<Vec<Grid<Rgb>>> {
+    let grid = part1_grid(data)?;
+    Ok(vec![grid.transform(|(_, cell)| match cell {
+        Cell::Obstruction => [64, 64, 64],
+        Cell::Visited => [224, 48, 48],
+        _ => [240, 240, 240],
+    })])
+}
+
 fn remove_guard(grid: &mut Grid<Cell>) -> Option<(Coord, Direction)> {
     let (coord, dir) = find_guard(grid)?;
     grid.set(&coord, Cell::Empty);
@@ -83,76 +100,47 @@ fn find_guard(grid: &Grid<Cell>) -> Option<(Coord, Direction)> {
         .find_map(|(coord, cell)| cell.guard_direction().map(|d| (coord, d)))
 }
 
+type Guard = (Coord, Direction);
+
+fn guard_step(grid: &Grid<Cell>, guard: Option<Guard>) -> Option<Guard> {
+    let (pos, dir) = guard?;
+    let proj_pos = grid.walk(&pos, dir)?;
+    match grid.get(&proj_pos) {
+        Cell::Empty => Some((proj_pos, dir)),
+        Cell::Obstruction | Cell::ObstructionOption => Some((pos, Turn::Right.apply(dir))),
+        Cell::GuardN
+        | Cell::GuardS
+        | Cell::GuardW
+        | Cell::GuardE
+        | Cell::Visited
+        | Cell::VisitedV
+        | Cell::VisitedH
+        | Cell::VisitedVH => {
+            unreachable!()
+        }
+    }
+}
+
 fn run_loop(
     grid: &Grid<Cell>,
     pos: Coord,
     dir: Direction,
     return_visited: bool,
 ) -> (IndexSet<(Coord, Direction)>, bool) {
-    let mut pos = pos;
-    let mut dir = dir;
-
-    let mut visited = vec![vec![[false; 4]; grid.w]; grid.h];
-    let mut looped = false;
-
-    loop {
-        let dir_u8: u8 = dir.into();
-        let already_visited = visited[pos.1][pos.0][dir_u8 as usize];
-        visited[pos.1][pos.0][dir as usize] = true;
-        if already_visited {
-            looped = true;
-            break;
-        }
+    let start = Some((pos, dir));
 
-        let proj_pos = grid.walk(&pos, dir);
-        if let Some(proj_pos) = proj_pos {
-            let mut next_pos = pos;
-            let mut next_dir = dir;
+    // leaving the grid is modeled as a fixed point (`None`), so the guard loops iff the cycle is elsewhere
+    let (_, cycle_state) = cycle::brent_period(&start, |guard| guard_step(grid, *guard));
+    let looped = cycle_state.is_some();
 
-            match grid.get(&proj_pos) {
-                Cell::Empty => {
-                    next_pos = proj_pos;
-                }
-                Cell::Obstruction | Cell::ObstructionOption => {
-                    next_dir = Turn::Right.apply(dir);
-                }
-                Cell::GuardN
-                | Cell::GuardS
-                | Cell::GuardW
-                | Cell::GuardE
-                | Cell::Visited
-                | Cell::VisitedV
-                | Cell::VisitedH
-                | Cell::VisitedVH => {
-                    unreachable!()
-                }
+    let mut visited = IndexSet::new();
+    if return_visited {
+        for guard in std::iter::successors(start, |guard| guard_step(grid, Some(*guard))) {
+            if !visited.insert(guard) {
+                break;
             }
-
-            pos = next_pos;
-            dir = next_dir;
-        } else {
-            break;
         }
     }
-
-    let visited = if return_visited {
-        let mut set = IndexSet::new();
-        for (y, row) in visited.iter().enumerate() {
-            for (x, dirs) in row.iter().enumerate() {
-                let coord = Coord(x, y);
-                for (dir, visited) in dirs.iter().enumerate() {
-                    if *visited {
-                        let dir_u8 = u8::try_from(dir).unwrap();
-                        let dir = Direction::try_from(dir_u8).unwrap();
-                        set.insert((coord, dir));
-                    }
-                }
-            }
-        }
-        set
-    } else {
-        IndexSet::new()
-    };
     (visited, looped)
 }
 
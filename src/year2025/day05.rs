@@ -1,7 +1,7 @@
 use crate::challenge::Day;
+use crate::utils::interval::IntervalSet;
 use anyhow::Result;
 use itertools::Itertools;
-use std::ops::RangeInclusive;
 
 pub fn day() -> Day<usize> {
     Day {
@@ -11,6 +11,7 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        ..Day::default()
     }
 }
 
@@ -21,11 +22,8 @@ fn part1(data: &str) -> Result<usize> {
     let mut fresh_count = 0;
     for line in lines_avail_ids {
         let id: usize = line.parse().unwrap();
-        for range in &fresh_ids {
-            if range.contains(&id) {
-                fresh_count += 1;
-                break;
-            }
+        if fresh_ids.contains(&id) {
+            fresh_count += 1;
         }
     }
 
@@ -34,51 +32,26 @@ fn part1(data: &str) -> Result<usize> {
 
 fn part2(data: &str) -> Result<usize> {
     let (fresh_ids, _) = parse_ranges(data);
-    let fresh_ids = merge_intervals(&fresh_ids);
-    let sum = fresh_ids
-        .iter()
-        .map(|range| range.end() - range.start() + 1)
-        .sum();
-    Ok(sum)
+    Ok(fresh_ids.total_len())
 }
 
-fn parse_ranges(data: &str) -> (Vec<RangeInclusive<usize>>, Vec<&str>) {
+fn parse_ranges(data: &str) -> (IntervalSet<usize>, Vec<&str>) {
     let lines = data.lines().collect_vec();
 
     // split on empty line
     let (lines_fresh_ids, lines_avail_ids) =
         lines.split(|line| line.is_empty()).collect_tuple().unwrap();
 
-    let mut fresh_ids = Vec::new();
-
     // each line in lines_fresh_ids is an inclusive range of ids
-    for line in lines_fresh_ids {
-        let (start, end) = line.split_once('-').unwrap();
-        let start: usize = start.parse().unwrap();
-        let end: usize = end.parse().unwrap();
-        fresh_ids.push(start..=end);
-    }
+    let fresh_ids = lines_fresh_ids
+        .iter()
+        .map(|line| {
+            let (start, end) = line.split_once('-').unwrap();
+            let start: usize = start.parse().unwrap();
+            let end: usize = end.parse().unwrap();
+            start..end + 1
+        })
+        .collect();
 
     (fresh_ids, lines_avail_ids.to_vec())
 }
-
-fn merge_intervals(intervals: &[RangeInclusive<usize>]) -> Vec<RangeInclusive<usize>> {
-    let mut result: Vec<RangeInclusive<usize>> = vec![];
-    for range in intervals.iter().sorted_by_key(|r| r.start()) {
-        if let Some(p) = result.last().cloned() {
-            if range.start() > p.end() {
-                result.push(range.clone());
-            } else if range.end() <= p.end() {
-                // skip
-            } else {
-                let merged = (*p.start())..=(*range.end());
-                let prev = result.pop();
-                assert_eq!(prev, Some(p));
-                result.push(merged);
-            }
-        } else {
-            result.push(range.clone());
-        }
-    }
-    result
-}
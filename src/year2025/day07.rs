@@ -1,8 +1,9 @@
-use crate::challenge::Day;
+use crate::challenge::{Day, diagnostic};
 use crate::utils::grid::{Coord, Direction, Grid};
+use crate::utils::memo::Memo;
 use anyhow::Result;
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use strum_macros::EnumString;
 
 pub fn day() -> Day<usize> {
@@ -13,6 +14,7 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        ..Day::default()
     }
 }
 
@@ -62,43 +64,31 @@ enum Cell {
 }
 
 fn part2(data: &str) -> Result<usize> {
-    use Cell::*;
     let grid: Grid<Cell> = data.parse()?;
-    let (start, _) = grid.iter().find(|(_, cell)| *cell == Start).unwrap();
+    let (start, _) = grid.iter().find(|(_, cell)| *cell == Cell::Start).unwrap();
 
-    // (x, timelines)
-    let mut beams = vec![(start.x(), 1)];
+    let mut memo = Memo::new();
+    let timelines = timelines(&mut memo, &grid, start);
+    diagnostic(|| format!("cache: {}", memo.stats()));
 
-    for y in 1..grid.h {
-        let mut new_beams = Vec::new();
-        for (x, timelines) in &beams {
-            match grid.get(&Coord(*x, y)) {
-                Empty => {
-                    // continue down
-                    new_beams.push((*x, *timelines));
-                }
-                Splitter => {
-                    // split
-                    new_beams.push((x - 1, *timelines));
-                    new_beams.push((x + 1, *timelines));
-                }
-                Start => panic!("unexpected cell"),
-            }
-        }
+    Ok(timelines)
+}
 
-        // combine beams at the same x position
-        let mut combined_beams: HashMap<usize, usize> = HashMap::new();
-        for (x, timelines) in new_beams {
-            *combined_beams.entry(x).or_default() += timelines;
+/// Number of timelines for a beam going down from `beam`.
+fn timelines(memo: &mut Memo<Coord, usize>, grid: &Grid<Cell>, beam: Coord) -> usize {
+    use Cell::*;
+    use Direction::*;
+    memo.get_or_insert_with(beam, |memo| {
+        let Some(next) = grid.walk(&beam, S) else {
+            return 1;
+        };
+        match grid.get(&next) {
+            Empty => timelines(memo, grid, next),
+            Splitter => {
+                timelines(memo, grid, grid.walk(&next, W).unwrap())
+                    + timelines(memo, grid, grid.walk(&next, E).unwrap())
+            }
+            Start => panic!("unexpected cell"),
         }
-
-        beams = combined_beams
-            .into_iter()
-            .sorted_by_key(|&(x, _)| x)
-            .collect();
-    }
-
-    let timelines = beams.iter().map(|(_, timelines)| timelines).sum();
-
-    Ok(timelines)
+    })
 }
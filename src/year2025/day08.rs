@@ -1,62 +1,63 @@
-use crate::challenge::Day;
+use crate::challenge::{Day, Parsed};
+use crate::utils::dsu::Kruskal;
+use crate::utils::kdtree::{ClosestPairs, KdTree};
 use crate::utils::point3::Point3;
-use anyhow::Result;
-use disjoint::DisjointSet;
+use anyhow::{Result, anyhow};
 use itertools::Itertools;
 
 pub fn day() -> Day<usize> {
-    Day {
-        part1_solutions: (40, Some(129564)),
-        part2_solutions: Some((25272, Some(42047840))),
-        part1_solver: part1,
-        part2_solver: part2,
-        source_file: file!(),
-        distinct_examples: false,
-    }
+    Day::parsed::<Puzzle>((40, Some(129564)), Some((25272, Some(42047840))), file!())
 }
 
-fn parts(data: &str) -> (usize, usize) {
-    let mut p1 = 0;
-    let mut p2 = 0;
+struct Puzzle;
+
+impl Parsed for Puzzle {
+    type Input = JunctionBoxes;
+    type Answer = usize;
 
-    let points = parse_input(data);
-    let example = points.first().unwrap().x == 162;
-    let mut pairs = (0..points.len())
-        .tuple_combinations()
-        .map(|(i, j)| (i, j, points[i].dist2(&points[j])))
-        .collect_vec();
-    pairs.sort_by_key(|(_, _, dist2)| *dist2);
+    fn parse(data: &str) -> Result<JunctionBoxes> {
+        let points = parse_input(data);
+        let tree = KdTree::new(&points);
+        Ok(JunctionBoxes { points, tree })
+    }
 
-    let p1_steps = if example { 10 } else { 1000 };
-    let mut ds = DisjointSet::with_len(points.len());
-    let mut steps = 0;
-    for (i, j, _) in pairs {
-        ds.join(i, j);
-        steps += 1;
-        let sets = ds.sets();
-        if steps == p1_steps {
-            let mut circuit_sizes = sets.iter().map(std::vec::Vec::len).collect_vec();
-            circuit_sizes.sort_unstable();
-            circuit_sizes.reverse();
-            p1 = circuit_sizes.iter().take(3).product();
-        }
-        if sets.len() == 1 {
-            p2 = points[i].x * points[j].x;
-            break;
-        }
+    fn part1(input: &JunctionBoxes) -> Result<usize> {
+        Ok(largest_circuits_product(input, 1000))
     }
 
-    (p1, p2)
+    fn part2(input: &JunctionBoxes) -> Result<usize> {
+        let last = connect_closest(&input.tree)
+            .find(|edge| edge.connected)
+            .ok_or_else(|| anyhow!("Not enough junction boxes"))?;
+        Ok(usize::try_from(
+            input.points[last.a].x * input.points[last.b].x,
+        )?)
+    }
+
+    /// The example only makes 10 connections.
+    fn part1_example(input: &JunctionBoxes) -> Result<usize> {
+        Ok(largest_circuits_product(input, 10))
+    }
 }
 
-fn part1(data: &str) -> Result<usize> {
-    Ok(parts(data).0)
+struct JunctionBoxes {
+    points: Vec<Point>,
+    tree: KdTree<Scalar>,
 }
-fn part2(data: &str) -> Result<usize> {
-    Ok(parts(data).1)
+
+/// Product of the sizes of the 3 largest circuits, after making the given number of connections.
+fn largest_circuits_product(input: &JunctionBoxes, connections: usize) -> usize {
+    let mut kruskal = connect_closest(&input.tree);
+    kruskal.by_ref().take(connections).for_each(drop);
+    kruskal.dsu().largest_components(3).iter().product()
 }
 
-type Scalar = usize;
+/// Connects pairs of junction boxes, closest first.
+fn connect_closest(tree: &KdTree<Scalar>) -> Kruskal<ClosestPairs<'_, Scalar>> {
+    Kruskal::new(tree.len(), tree.closest_pairs())
+}
+
+type Scalar = i64;
 type Point = Point3<Scalar>;
 
 fn parse_input(data: &str) -> Vec<Point> {
@@ -71,3 +72,15 @@ fn parse_input(data: &str) -> Vec<Point> {
     }
     points
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let data = day().read_data_file("example").unwrap();
+        let input = Puzzle::parse(&data).unwrap();
+        assert_eq!(largest_circuits_product(&input, 10), 40);
+    }
+}
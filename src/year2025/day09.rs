@@ -1,4 +1,5 @@
 use crate::challenge::Day;
+use crate::utils::geometry::{Polygon, RectilinearRegion};
 use anyhow::Result;
 use itertools::Itertools;
 use std::cmp::{max, min};
@@ -6,14 +7,16 @@ use std::cmp::{max, min};
 pub fn day() -> Day<usize> {
     Day {
         part1_solutions: (50, Some(4750297200)),
-        part2_solutions: None,
+        part2_solutions: Some((24, Some(1578115935))),
         part1_solver: part1,
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        ..Day::default()
     }
 }
 
+#[allow(clippy::unnecessary_cast)]
 fn part1(data: &str) -> Result<usize> {
     let locs = parse(data);
     let max = locs
@@ -31,8 +34,23 @@ fn part1(data: &str) -> Result<usize> {
     Ok(max as usize)
 }
 
-fn part2(_data: &str) -> Result<usize> {
-    todo!()
+fn part2(data: &str) -> Result<usize> {
+    let locs = parse(data);
+    let polygon = Polygon::new(
+        locs.iter()
+            .map(|&(x, y)| (i64::try_from(x).unwrap(), i64::try_from(y).unwrap()))
+            .collect(),
+    );
+    let region = RectilinearRegion::new(&polygon);
+    let max = polygon
+        .vertices()
+        .iter()
+        .tuple_combinations()
+        .filter(|&(&a, &b)| region.contains_rect(a, b))
+        .map(|((x1, y1), (x2, y2))| (x1.abs_diff(*x2) + 1) * (y1.abs_diff(*y2) + 1))
+        .max()
+        .unwrap();
+    Ok(usize::try_from(max)?)
 }
 
 fn parse(data: &str) -> Vec<(usize, usize)> {
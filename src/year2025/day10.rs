@@ -1,7 +1,8 @@
 use crate::challenge::Day;
-use crate::utils::f64_conversions::try_usize_from_f64;
+#[cfg(feature = "good_lp")]
+use crate::challenge::{Part, Variant};
+use crate::utils::lp::IntegerProgram;
 use anyhow::{Result, anyhow, ensure};
-use itertools::Itertools;
 use nom::IResult;
 use nom::Parser;
 use nom::branch::alt;
@@ -20,6 +21,16 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: false,
+        // without good_lp, part 2 is already solved exactly
+        variants: vec![
+            #[cfg(feature = "good_lp")]
+            Variant {
+                part: Part::Part2,
+                name: "exact",
+                solver: part2_exact,
+            },
+        ],
+        ..Day::default()
     }
 }
 
@@ -68,9 +79,18 @@ fn part1(data: &str) -> Result<usize> {
 }
 
 fn part2(data: &str) -> Result<usize> {
-    use good_lp::Expression;
-    use good_lp::{IntoAffineExpression, ProblemVariables, SolverModel, highs, variable};
+    min_total_presses(data, IntegerProgram::solve)
+}
+
+/// Same as part 2, using the exact solver rather than `good_lp`.
+#[cfg(feature = "good_lp")]
+fn part2_exact(data: &str) -> Result<usize> {
+    min_total_presses(data, IntegerProgram::solve_exact)
+}
 
+type ProgramSolver = fn(&IntegerProgram) -> Result<Option<Vec<i64>>>;
+
+fn min_total_presses(data: &str, solve: ProgramSolver) -> Result<usize> {
     let machine_defs = data
         .lines()
         .map(str::parse::<MachineDef>)
@@ -79,41 +99,10 @@ fn part2(data: &str) -> Result<usize> {
     let mut sum = 0;
 
     for md in &machine_defs {
-        let mut vars = ProblemVariables::new();
-
-        // variables for number of times each button is pressed
-        let var_btns = (0..md.buttons.len())
-            .map(|_| vars.add(variable().integer().min(0)))
-            .collect_vec();
-
-        // constraints for each joltage counter
-        let constraints = md
-            .joltage_reqs
-            .iter()
-            .enumerate()
-            .map(|(idx, req)| {
-                let mut joltage_expr = 0.into_expression();
-                for (btn, var_btn) in md.buttons.iter().zip(var_btns.iter()) {
-                    for x in btn {
-                        if *x == idx {
-                            joltage_expr += var_btn;
-                        }
-                    }
-                }
-
-                joltage_expr.eq(i32::try_from(*req).unwrap())
-            })
-            .collect_vec();
-
-        let total_presses: Expression = var_btns.iter().sum();
-        let mut problem = vars.minimise(&total_presses).using(highs);
-        for c in constraints {
-            problem.add_constraint(c);
-        }
-        let solution = problem.solve()?;
-        let sum_solution = total_presses.eval_with(&solution);
-
-        sum += try_usize_from_f64(sum_solution.round()).unwrap();
+        let program = md.joltage_program()?;
+        let presses = solve(&program)?
+            .ok_or_else(|| anyhow!("No way to reach joltages {:?}", md.joltage_reqs))?;
+        sum += usize::try_from(program.objective(&presses))?;
     }
 
     Ok(sum)
@@ -142,6 +131,25 @@ impl MachineDef {
             joltage_reqs,
         })
     }
+
+    /// Minimize the total number of presses, with one variable per button and one constraint per
+    /// joltage counter.
+    fn joltage_program(&self) -> Result<IntegerProgram> {
+        let a = (0..self.joltage_reqs.len())
+            .map(|idx| {
+                self.buttons
+                    .iter()
+                    .map(|btn| i64::from(btn.contains(&idx)))
+                    .collect()
+            })
+            .collect();
+        let b = self
+            .joltage_reqs
+            .iter()
+            .map(|&req| i64::try_from(req))
+            .collect::<Result<_, _>>()?;
+        IntegerProgram::new(a, b, vec![1; self.buttons.len()])
+    }
 }
 impl FromStr for MachineDef {
     type Err = anyhow::Error;
@@ -199,3 +207,19 @@ fn joltage_reqs(input: &str) -> IResult<&str, Vec<usize>> {
     )
     .parse(input)
 }
+
+#[cfg(all(test, feature = "good_lp"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_solver_agrees() {
+        let data = day().read_data_file("input").unwrap();
+        for md in data.lines().map(str::parse::<MachineDef>) {
+            let program = md.unwrap().joltage_program().unwrap();
+            let solution = program.solve().unwrap().unwrap();
+            let exact = program.solve_exact().unwrap().unwrap();
+            assert_eq!(program.objective(&exact), program.objective(&solution));
+        }
+    }
+}
@@ -1,8 +1,7 @@
 use crate::challenge::Day;
-use anyhow::Result;
-use itertools::Itertools;
-use pathfinding::prelude::count_paths;
-use std::collections::HashMap;
+use crate::utils::graph::Graph;
+use crate::utils::graph_export::{GraphExport, Shape};
+use anyhow::{Context, Result};
 
 pub fn day() -> Day<usize> {
     Day {
@@ -12,62 +11,47 @@ pub fn day() -> Day<usize> {
         part2_solver: part2,
         source_file: file!(),
         distinct_examples: true,
+        graph_export: Some(graph),
+        ..Day::default()
     }
 }
 
 fn part1(data: &str) -> Result<usize> {
     let flow = parse(data);
-    let successors = |pos: &String| flow.get(pos).unwrap().iter().cloned();
-    let start = "you".to_owned();
-    let count = count_paths(start, successors, |pos| *pos == "out");
-    Ok(count)
+    let id = |name| flow.id(name).context(format!("Unknown device {name}"));
+    let count = flow
+        .count_paths(id("you")?, id("out")?)
+        .context("Infinitely many paths")?;
+    Ok(usize::try_from(count)?)
 }
 
 fn part2(data: &str) -> Result<usize> {
-    #[derive(Eq, PartialEq, Hash, Clone)]
-    struct Pos {
-        node: String,
-        visited_dac: bool,
-        visited_fft: bool,
-    }
-    impl Pos {
-        fn start() -> Self {
-            Self {
-                node: "svr".to_owned(),
-                visited_dac: false,
-                visited_fft: false,
-            }
-        }
-        fn successors(&self, flow: &HashMap<String, Vec<String>>) -> Vec<Self> {
-            if let Some(outputs) = flow.get(&self.node) {
-                outputs
-                    .iter()
-                    .map(|next_node| Pos {
-                        node: next_node.clone(),
-                        visited_dac: self.visited_dac || next_node == "dac",
-                        visited_fft: self.visited_fft || next_node == "fft",
-                    })
-                    .collect_vec()
-            } else {
-                vec![]
-            }
-        }
-        fn success(&self) -> bool {
-            self.node == "out" && self.visited_dac && self.visited_fft
-        }
-    }
+    let flow = parse(data);
+    let id = |name| flow.id(name).context(format!("Unknown device {name}"));
+    let count = flow
+        .count_paths_via(id("svr")?, id("out")?, &[id("dac")?, id("fft")?])
+        .context("Infinitely many paths")?;
+    Ok(usize::try_from(count)?)
+}
 
+fn graph(data: &str) -> Result<GraphExport> {
     let flow = parse(data);
-    let count = count_paths(Pos::start(), |pos| pos.successors(&flow), Pos::success);
-    Ok(count)
+    let mut export = GraphExport::from(&flow);
+    for device in ["you", "svr", "dac", "fft", "out"] {
+        if flow.id(device).is_some() {
+            export.node(device, Shape::DoubleCircle);
+        }
+    }
+    Ok(export)
 }
 
-fn parse(data: &str) -> HashMap<String, Vec<String>> {
-    data.lines()
-        .map(|line| {
-            let (device, rest) = line.split_once(':').unwrap();
-            let outputs = rest.split_whitespace().map(ToOwned::to_owned).collect_vec();
-            (device.to_owned(), outputs)
-        })
-        .collect()
+fn parse(data: &str) -> Graph {
+    let mut flow = Graph::new();
+    for line in data.lines() {
+        let (device, rest) = line.split_once(':').unwrap();
+        for output in rest.split_whitespace() {
+            flow.add_edge(device, output);
+        }
+    }
+    flow
 }